@@ -0,0 +1,197 @@
+//! Credentials attached to the requests sent to an endpoint.
+
+use std::error::Error;
+use std::fmt;
+use std::sync::Arc;
+
+use futures_util::future::BoxFuture;
+use reqwest::RequestBuilder;
+use thiserror::Error;
+
+pub use reqwest::header::HeaderValue;
+
+/// How requests authenticate to the endpoint.
+///
+/// Set on every endpoint of a client with [`SparqlClient::with_auth`], or on a
+/// single endpoint with [`Endpoint::with_auth`].
+///
+/// The [`Debug`] output never shows passwords, tokens or header values.
+///
+/// ```
+/// use sparql_http_client::{Auth, Endpoint, SparqlClient};
+///
+/// let endpoint = Endpoint::new(SparqlClient::default(), "http://localhost:7200/repositories/kb")
+///     .with_auth(Auth::basic("admin", Some("s3cret")));
+///
+/// assert!(!format!("{endpoint:?}").contains("s3cret"));
+/// ```
+///
+/// [`SparqlClient::with_auth`]: crate::SparqlClient::with_auth
+/// [`Endpoint::with_auth`]: crate::Endpoint::with_auth
+#[derive(Clone)]
+pub enum Auth {
+    /// HTTP Basic authentication.
+    Basic {
+        username: Box<str>,
+        password: Option<Box<str>>,
+    },
+    /// A static `Bearer` token.
+    Bearer(Box<str>),
+    /// An `Authorization` header obtained from a [`CredentialProvider`] before
+    /// every request.
+    Provider(Arc<dyn CredentialProvider>),
+}
+
+impl Auth {
+    /// HTTP Basic authentication with `username` and an optional `password`.
+    pub fn basic(username: &str, password: Option<&str>) -> Self {
+        Self::Basic {
+            username: username.into(),
+            password: password.map(Into::into),
+        }
+    }
+
+    /// A static `Bearer` token.
+    pub fn bearer(token: &str) -> Self {
+        Self::Bearer(token.into())
+    }
+
+    /// Credentials obtained from `provider` before every request.
+    pub fn provider(provider: impl CredentialProvider + 'static) -> Self {
+        Self::Provider(Arc::new(provider))
+    }
+
+    /// Adds the `Authorization` header to `request`.
+    pub(crate) async fn apply(
+        &self,
+        request: RequestBuilder,
+    ) -> Result<RequestBuilder, CredentialError> {
+        Ok(match self {
+            Self::Basic { username, password } => request.basic_auth(username, password.as_ref()),
+            Self::Bearer(token) => request.bearer_auth(token),
+            Self::Provider(provider) => {
+                let mut value = provider.authorization().await?;
+                value.set_sensitive(true);
+                request.header(reqwest::header::AUTHORIZATION, value)
+            }
+        })
+    }
+}
+
+impl fmt::Debug for Auth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const REDACTED: &str = "<redacted>";
+        match self {
+            Self::Basic { username, password } => f
+                .debug_struct("Basic")
+                .field("username", username)
+                .field("password", &password.as_ref().map(|_| REDACTED))
+                .finish(),
+            Self::Bearer(_) => f.debug_tuple("Bearer").field(&REDACTED).finish(),
+            Self::Provider(_) => f.debug_tuple("Provider").finish_non_exhaustive(),
+        }
+    }
+}
+
+/// A source of `Authorization` header values, asked before every request.
+///
+/// Implement it for tokens that expire, such as OAuth2 client-credentials
+/// tokens: cache the token and fetch a new one when it is about to expire.
+///
+/// ```
+/// use std::time::{Duration, Instant};
+///
+/// use futures_util::future::BoxFuture;
+/// use sparql_http_client::auth::{CredentialError, CredentialProvider, HeaderValue};
+/// use sparql_http_client::{Auth, Endpoint, SparqlClient};
+/// use tokio::sync::Mutex;
+///
+/// struct ClientCredentials {
+///     cached: Mutex<Option<(String, Instant)>>,
+/// }
+///
+/// async fn fetch_token() -> Result<(String, Duration), std::io::Error> {
+///     // POST to the token endpoint of the authorization server…
+///     Ok(("eyJhbGciOi…".into(), Duration::from_secs(3600)))
+/// }
+///
+/// impl CredentialProvider for ClientCredentials {
+///     fn authorization(&self) -> BoxFuture<'_, Result<HeaderValue, CredentialError>> {
+///         Box::pin(async move {
+///             let mut cached = self.cached.lock().await;
+///             let token = match &*cached {
+///                 Some((token, expiry)) if *expiry > Instant::now() => token.clone(),
+///                 _ => {
+///                     let (token, lifetime) = fetch_token().await.map_err(CredentialError::new)?;
+///                     *cached = Some((token.clone(), Instant::now() + lifetime / 2));
+///                     token
+///                 }
+///             };
+///             HeaderValue::try_from(format!("Bearer {token}")).map_err(CredentialError::new)
+///         })
+///     }
+/// }
+///
+/// let client = SparqlClient::default().with_auth(Auth::provider(ClientCredentials {
+///     cached: Mutex::new(None),
+/// }));
+/// ```
+pub trait CredentialProvider: Send + Sync {
+    /// Returns the value of the `Authorization` header of the next request.
+    fn authorization(&self) -> BoxFuture<'_, Result<HeaderValue, CredentialError>>;
+}
+
+/// A [`CredentialProvider`] failed to provide credentials.
+#[derive(Debug, Error)]
+#[error("failed to obtain credentials: {0}")]
+pub struct CredentialError(#[source] Box<dyn Error + Send + Sync>);
+
+impl CredentialError {
+    /// Wraps the error that prevented obtaining credentials.
+    pub fn new(error: impl Into<Box<dyn Error + Send + Sync>>) -> Self {
+        Self(error.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Static;
+
+    impl CredentialProvider for Static {
+        fn authorization(&self) -> BoxFuture<'_, Result<HeaderValue, CredentialError>> {
+            Box::pin(async { Ok(HeaderValue::from_static("Token abc")) })
+        }
+    }
+
+    async fn authorization(auth: Auth) -> HeaderValue {
+        let request = reqwest::Client::new().get("http://localhost/");
+        let request = auth.apply(request).await.unwrap().build().unwrap();
+        let value = request.headers()[reqwest::header::AUTHORIZATION].clone();
+        assert!(value.is_sensitive());
+        value
+    }
+
+    #[tokio::test]
+    async fn headers() {
+        let basic = authorization(Auth::basic("user", Some("pass"))).await;
+        assert_eq!(basic, "Basic dXNlcjpwYXNz");
+        assert_eq!(authorization(Auth::bearer("t0k")).await, "Bearer t0k");
+        assert_eq!(authorization(Auth::provider(Static)).await, "Token abc");
+    }
+
+    #[test]
+    fn redacted_debug() {
+        let debug = format!(
+            "{:?} {:?} {:?}",
+            Auth::basic("user", Some("pass")),
+            Auth::bearer("t0k"),
+            Auth::provider(Static)
+        );
+        assert_eq!(
+            debug,
+            r#"Basic { username: "user", password: Some("<redacted>") } Bearer("<redacted>") Provider(..)"#
+        );
+    }
+}
@@ -1,9 +1,17 @@
+use std::sync::Arc;
+use std::time::Duration;
+
 use reqwest::{
-    header::{HeaderValue, ACCEPT, USER_AGENT},
-    RequestBuilder,
+    header::{HeaderValue, ACCEPT, CONTENT_TYPE, USER_AGENT},
+    RequestBuilder, Url,
 };
+use tokio::sync::OwnedSemaphorePermit;
 
-use crate::query::{QueryString, SparqlQuery};
+use crate::auth::{Auth, CredentialError};
+use crate::query::{QueryString, SparqlQuery, UpdateString};
+use crate::rate_limit::{Limiter, RateLimit};
+use crate::response::{ResultFormat, StatusError, UpdateError, UpdateResponse};
+use crate::retry::RetryPolicy;
 
 /// Identifies this client to the SPARQL endpoint via the HTTP `User-Agent` header.
 ///
@@ -73,6 +81,7 @@ impl UserAgent {
 pub struct SparqlClient {
     inner: reqwest::Client,
     agent: UserAgent,
+    auth: Option<Auth>,
 }
 
 impl SparqlClient {
@@ -81,14 +90,281 @@ impl SparqlClient {
         Self {
             inner: reqwest::Client::new(),
             agent: user_agent,
+            auth: None,
+        }
+    }
+
+    /// Authenticates the requests to every endpoint using this client with `auth`.
+    ///
+    /// Endpoints can use other credentials with
+    /// [`Endpoint::with_auth`](Endpoint::with_auth).
+    ///
+    /// ```
+    /// use sparql_http_client::{Auth, SparqlClient};
+    ///
+    /// let client = SparqlClient::default().with_auth(Auth::bearer("eyJhbGciOi…"));
+    /// ```
+    pub fn with_auth(mut self, auth: Auth) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
+    /// Identifies this client with `user_agent`.
+    pub fn with_user_agent(mut self, user_agent: UserAgent) -> Self {
+        self.agent = user_agent;
+        self
+    }
+
+    /// Returns a builder to configure the underlying HTTP client.
+    pub fn builder() -> SparqlClientBuilder {
+        SparqlClientBuilder::default()
+    }
+}
+
+/// Wraps an existing [`reqwest::Client`], keeping its configuration and
+/// sharing its connection pool.
+///
+/// ```
+/// use sparql_http_client::SparqlClient;
+///
+/// let http = reqwest::Client::new();
+/// let client = SparqlClient::from(http.clone());
+/// ```
+impl From<reqwest::Client> for SparqlClient {
+    fn from(inner: reqwest::Client) -> Self {
+        Self {
+            inner,
+            agent: UserAgent::default(),
+            auth: None,
         }
     }
 }
 
+/// Configures the HTTP client of a [`SparqlClient`].
+///
+/// Created by [`SparqlClient::builder`]. Options not set keep the defaults of
+/// [`reqwest::ClientBuilder`]: no timeouts, the system proxy and the platform's
+/// root certificates.
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use sparql_http_client::{reqwest, SparqlClient, UserAgent};
+///
+/// # fn main() -> Result<(), reqwest::Error> {
+/// let client = SparqlClient::builder()
+///     .with_user_agent(UserAgent {
+///         name: "my-app".into(),
+///         version: "1.0.0".into(),
+///         contact: "mailto:user@example.com".into(),
+///     })
+///     .with_connect_timeout(Duration::from_secs(5))
+///     .with_read_timeout(Duration::from_secs(60))
+///     .with_proxy(reqwest::Proxy::all("http://proxy.internal:3128")?)
+///     .with_pool_idle_timeout(Duration::from_secs(30))
+///     .build()?;
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Default)]
+pub struct SparqlClientBuilder {
+    inner: reqwest::ClientBuilder,
+    agent: UserAgent,
+    auth: Option<Auth>,
+}
+
+impl SparqlClientBuilder {
+    /// Identifies the client with `user_agent`.
+    pub fn with_user_agent(mut self, user_agent: UserAgent) -> Self {
+        self.agent = user_agent;
+        self
+    }
+
+    /// Authenticates every request with `auth`, see [`SparqlClient::with_auth`].
+    pub fn with_auth(mut self, auth: Auth) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
+    /// Limits the time taken to establish a connection.
+    pub fn with_connect_timeout(mut self, timeout: Duration) -> Self {
+        self.inner = self.inner.connect_timeout(timeout);
+        self
+    }
+
+    /// Limits the time waited for each read of the response, so that a stalled
+    /// stream of results fails while a long one that keeps flowing does not.
+    pub fn with_read_timeout(mut self, timeout: Duration) -> Self {
+        self.inner = self.inner.read_timeout(timeout);
+        self
+    }
+
+    /// Limits the total time of a request, from connecting until the response
+    /// body has been read. This includes streaming every result row.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.inner = self.inner.timeout(timeout);
+        self
+    }
+
+    /// Sends requests through `proxy`. Can be called several times, the first
+    /// proxy matching a URL is used.
+    pub fn with_proxy(mut self, proxy: reqwest::Proxy) -> Self {
+        self.inner = self.inner.proxy(proxy);
+        self
+    }
+
+    /// Ignores the proxies configured in the environment.
+    pub fn without_proxy(mut self) -> Self {
+        self.inner = self.inner.no_proxy();
+        self
+    }
+
+    /// Trusts `certificate` as a root certificate, in addition to the
+    /// platform's, e.g. for an endpoint behind a company CA.
+    pub fn with_root_certificate(mut self, certificate: reqwest::Certificate) -> Self {
+        self.inner = self.inner.add_root_certificate(certificate);
+        self
+    }
+
+    /// Presents `identity` as client certificate, for endpoints that require
+    /// mutual TLS.
+    pub fn with_identity(mut self, identity: reqwest::Identity) -> Self {
+        self.inner = self.inner.identity(identity);
+        self
+    }
+
+    /// Closes connections that stay idle in the pool for longer than `timeout`.
+    pub fn with_pool_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.inner = self.inner.pool_idle_timeout(timeout);
+        self
+    }
+
+    /// Keeps at most `max` idle connections per host in the pool.
+    pub fn with_pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.inner = self.inner.pool_max_idle_per_host(max);
+        self
+    }
+
+    /// Speaks HTTP/2 from the start of every connection, without negotiating
+    /// it, for endpoints known to support it. Over TLS, HTTP/2 is otherwise
+    /// used whenever the server offers it.
+    pub fn with_http2_prior_knowledge(mut self) -> Self {
+        self.inner = self.inner.http2_prior_knowledge();
+        self
+    }
+
+    /// Asks for gzip-compressed responses and decompresses them transparently.
+    ///
+    /// Enabled by default with the `gzip` feature.
+    #[cfg(feature = "gzip")]
+    pub fn with_gzip(mut self, enable: bool) -> Self {
+        self.inner = self.inner.gzip(enable);
+        self
+    }
+
+    /// Asks for brotli-compressed responses and decompresses them transparently.
+    ///
+    /// Enabled by default with the `brotli` feature.
+    #[cfg(feature = "brotli")]
+    pub fn with_brotli(mut self, enable: bool) -> Self {
+        self.inner = self.inner.brotli(enable);
+        self
+    }
+
+    /// Builds the client.
+    ///
+    /// Fails if the TLS backend cannot be initialized, or rejects a
+    /// certificate or identity.
+    pub fn build(self) -> Result<SparqlClient, reqwest::Error> {
+        Ok(SparqlClient {
+            inner: self.inner.build()?,
+            agent: self.agent,
+            auth: self.auth,
+        })
+    }
+}
+
+/// How a query is sent to the endpoint, among the operations the SPARQL
+/// protocol defines.
+///
+/// Set for every query with [`Endpoint::with_query_method`], or for a single
+/// query with [`SparqlQuery::with_query_method`].
+///
+/// ```
+/// use sparql_http_client::{Endpoint, QueryMethod, SparqlClient};
+///
+/// // GET for queries whose URL fits in 2048 bytes, so that caches can serve them.
+/// let endpoint = Endpoint::new(SparqlClient::default(), "https://example.org/sparql")
+///     .with_query_method(QueryMethod::AUTO);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QueryMethod {
+    /// A `GET` request with the query and dataset in the URL.
+    ///
+    /// The only method HTTP caches store, but long queries can exceed the URL
+    /// length limit of the endpoint or of proxies on the way.
+    Get,
+    /// A `POST` request with the query and dataset as an
+    /// `application/x-www-form-urlencoded` body.
+    #[default]
+    UrlEncodedPost,
+    /// A `POST` request with the query as an `application/sparql-query` body,
+    /// and the dataset in the URL.
+    DirectPost,
+    /// [`Get`](QueryMethod::Get) when the resulting URL is at most
+    /// `max_url_length` bytes long, and
+    /// [`UrlEncodedPost`](QueryMethod::UrlEncodedPost) otherwise.
+    Auto {
+        /// The length of the longest URL sent with `GET`.
+        max_url_length: usize,
+    },
+}
+
+impl QueryMethod {
+    /// [`Auto`](QueryMethod::Auto) with a limit of 2048 bytes, which most
+    /// servers and proxies accept.
+    pub const AUTO: Self = Self::Auto {
+        max_url_length: 2048,
+    };
+}
+
+/// The RDF dataset of a request, sent as the `default-graph-uri` and
+/// `named-graph-uri` parameters of the SPARQL protocol.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct Dataset {
+    default_graphs: Vec<Box<str>>,
+    named_graphs: Vec<Box<str>>,
+}
+
+impl Dataset {
+    pub(crate) fn add_default_graph(&mut self, iri: &str) {
+        self.default_graphs.push(iri.into());
+    }
+
+    pub(crate) fn add_named_graph(&mut self, iri: &str) {
+        self.named_graphs.push(iri.into());
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.default_graphs.is_empty() && self.named_graphs.is_empty()
+    }
+
+    /// Returns the protocol parameters, one per graph.
+    pub(crate) fn params(&self) -> impl Iterator<Item = (&'static str, &str)> {
+        let default = self
+            .default_graphs
+            .iter()
+            .map(|g| ("default-graph-uri", &**g));
+        let named = self.named_graphs.iter().map(|g| ("named-graph-uri", &**g));
+        default.chain(named)
+    }
+}
+
 /// A SPARQL endpoint that executes queries over HTTP.
 ///
 /// Combines an HTTP client with a URL. Create executable queries with
-/// [`build_query`](Endpoint::build_query) or the [`query!`](crate::query!) macro.
+/// [`build_query`](Endpoint::build_query) or the [`query!`](crate::query!) macro,
+/// and run updates with [`update`](Endpoint::update) or the
+/// [`update!`](crate::update!) macro.
 ///
 /// `Endpoint` is cheap to clone — the underlying [`reqwest::Client`] shares
 /// its connection pool via reference counting.
@@ -106,32 +382,227 @@ impl SparqlClient {
 #[derive(Debug, Clone)]
 pub struct Endpoint {
     url: Box<str>,
+    update_url: Option<Box<str>>,
+    result_format: ResultFormat,
+    query_method: QueryMethod,
+    dataset: Dataset,
+    auth: Option<Auth>,
+    retry_policy: Option<RetryPolicy>,
+    limiter: Option<Arc<Limiter>>,
     client: SparqlClient,
 }
 
 impl Endpoint {
     /// Creates a new endpoint from a client and a URL.
+    ///
+    /// Updates are sent to the same URL unless
+    /// [`with_update_url`](Endpoint::with_update_url) is called.
     pub fn new(client: SparqlClient, url: &str) -> Self {
         Self {
             url: url.into(),
+            update_url: None,
+            result_format: ResultFormat::default(),
+            query_method: QueryMethod::default(),
+            dataset: Dataset::default(),
+            auth: None,
+            retry_policy: None,
+            limiter: None,
             client,
         }
     }
 
-    fn request_with_accept(&self, accept: &'static str) -> RequestBuilder {
+    /// Sets the format SELECT and ASK results are preferably requested in.
+    /// Defaults to [`ResultFormat::Tsv`].
+    ///
+    /// The other supported formats are still offered with a lower weight in the
+    /// `Accept` header, and the response is parsed according to the format the
+    /// endpoint actually returns.
+    ///
+    /// Individual queries can override it with
+    /// [`SparqlQuery::with_result_format`](crate::SparqlQuery::with_result_format).
+    ///
+    /// ```
+    /// use sparql_http_client::{Endpoint, SparqlClient};
+    /// use sparql_http_client::response::ResultFormat;
+    ///
+    /// let endpoint = Endpoint::new(SparqlClient::default(), "https://example.org/sparql")
+    ///     .with_result_format(ResultFormat::Json);
+    /// ```
+    pub fn with_result_format(mut self, format: ResultFormat) -> Self {
+        self.result_format = format;
+        self
+    }
+
+    pub(crate) fn result_format(&self) -> ResultFormat {
+        self.result_format
+    }
+
+    /// Authenticates the requests to this endpoint with `auth`, instead of the
+    /// credentials of its client set with [`SparqlClient::with_auth`].
+    pub fn with_auth(mut self, auth: Auth) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
+    /// Retries queries that fail with a transient error according to `policy`.
+    ///
+    /// ```
+    /// use sparql_http_client::{Endpoint, RetryPolicy, SparqlClient};
+    ///
+    /// let endpoint = Endpoint::new(SparqlClient::default(), "https://query.wikidata.org/sparql")
+    ///     .with_retry_policy(RetryPolicy::default());
+    /// ```
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(policy);
+        self
+    }
+
+    pub(crate) fn retry_policy(&self) -> Option<&RetryPolicy> {
+        self.retry_policy.as_ref()
+    }
+
+    /// Limits the requests in flight to this endpoint and its clones.
+    ///
+    /// The limit is shared with the clones made afterwards; calling this on a
+    /// clone gives it limits of its own.
+    pub fn with_rate_limit(mut self, limit: RateLimit) -> Self {
+        self.limiter = Some(Arc::new(Limiter::new(limit)));
+        self
+    }
+
+    /// Waits for a request to be allowed in flight by the rate limit. The
+    /// permit must be kept until the response has been read.
+    pub(crate) async fn acquire(&self) -> Option<OwnedSemaphorePermit> {
+        self.limiter.as_ref()?.acquire().await
+    }
+
+    /// Waits until the rate limit allows sending a request.
+    pub(crate) async fn wait_for_window(&self) {
+        if let Some(limiter) = &self.limiter {
+            limiter.wait_for_window().await;
+        }
+    }
+
+    /// Adds the credentials of this endpoint, or of its client, to `request`.
+    pub(crate) async fn authorize(
+        &self,
+        request: RequestBuilder,
+    ) -> Result<RequestBuilder, CredentialError> {
+        match self.auth.as_ref().or(self.client.auth.as_ref()) {
+            Some(auth) => auth.apply(request).await,
+            None => Ok(request),
+        }
+    }
+
+    /// Sets how queries are sent. Defaults to [`QueryMethod::UrlEncodedPost`].
+    ///
+    /// Individual queries can override it with
+    /// [`SparqlQuery::with_query_method`](crate::SparqlQuery::with_query_method).
+    pub fn with_query_method(mut self, method: QueryMethod) -> Self {
+        self.query_method = method;
+        self
+    }
+
+    pub(crate) fn query_method(&self) -> QueryMethod {
+        self.query_method
+    }
+
+    /// Adds `iri` to the default graph of every query sent to this endpoint,
+    /// with the `default-graph-uri` protocol parameter.
+    ///
+    /// The default graph is the merge of all the graphs added. A query that
+    /// sets its own dataset with
+    /// [`SparqlQuery::with_default_graph`](crate::SparqlQuery::with_default_graph)
+    /// or [`SparqlQuery::with_named_graph`](crate::SparqlQuery::with_named_graph)
+    /// replaces these defaults entirely.
+    ///
+    /// ```
+    /// use sparql_http_client::{Endpoint, SparqlClient};
+    ///
+    /// let tenant = Endpoint::new(SparqlClient::default(), "http://localhost:3030/ds/query")
+    ///     .with_default_graph("http://example.org/tenants/acme")
+    ///     .with_named_graph("http://example.org/tenants/acme/audit");
+    /// ```
+    pub fn with_default_graph(mut self, iri: &str) -> Self {
+        self.dataset.add_default_graph(iri);
+        self
+    }
+
+    /// Adds `iri` to the named graphs of every query sent to this endpoint,
+    /// with the `named-graph-uri` protocol parameter.
+    ///
+    /// See [`with_default_graph`](Endpoint::with_default_graph).
+    pub fn with_named_graph(mut self, iri: &str) -> Self {
+        self.dataset.add_named_graph(iri);
+        self
+    }
+
+    pub(crate) fn dataset(&self) -> &Dataset {
+        &self.dataset
+    }
+
+    /// Sends updates to `url` instead of the query URL.
+    ///
+    /// Many stores expose updates on a separate path, e.g. Fuseki's `/update`:
+    ///
+    /// ```
+    /// use sparql_http_client::{Endpoint, SparqlClient};
+    ///
+    /// let endpoint = Endpoint::new(SparqlClient::default(), "http://localhost:3030/ds/query")
+    ///     .with_update_url("http://localhost:3030/ds/update");
+    /// ```
+    pub fn with_update_url(mut self, url: &str) -> Self {
+        self.update_url = Some(url.into());
+        self
+    }
+
+    fn post(&self, url: &str) -> RequestBuilder {
         self.client
             .inner
-            .post(&*self.url)
-            .header(ACCEPT, HeaderValue::from_static(accept))
+            .post(url)
             .header(USER_AGENT, self.client.agent.header_value())
     }
 
-    pub(crate) fn request(&self) -> RequestBuilder {
-        self.request_with_accept("application/sparql-results+json")
+    /// Builds the request for a query, where `params` starts with the `query`
+    /// parameter and continues with the dataset.
+    pub(crate) fn query_request(
+        &self,
+        method: QueryMethod,
+        params: &[(&str, &str)],
+        accept: HeaderValue,
+    ) -> RequestBuilder {
+        let request = match method {
+            QueryMethod::Get => self.get(params),
+            QueryMethod::UrlEncodedPost => self.post(&self.url).form(params),
+            QueryMethod::DirectPost => {
+                let (query, dataset) = params.split_first().expect("query parameter");
+                self.post(&self.url)
+                    .query(dataset)
+                    .header(CONTENT_TYPE, "application/sparql-query")
+                    .body(query.1.to_owned())
+            }
+            QueryMethod::Auto { max_url_length } => {
+                // An unparsable URL is left for `send` to report.
+                let fits = Url::parse(&self.url).is_ok_and(|mut url| {
+                    url.query_pairs_mut().extend_pairs(params);
+                    url.as_str().len() <= max_url_length
+                });
+                if fits {
+                    self.get(params)
+                } else {
+                    self.post(&self.url).form(params)
+                }
+            }
+        };
+        request.header(ACCEPT, accept)
     }
 
-    pub(crate) fn request_tsv(&self) -> RequestBuilder {
-        self.request_with_accept("text/tab-separated-values")
+    fn get(&self, params: &[(&str, &str)]) -> RequestBuilder {
+        self.client
+            .inner
+            .get(&*self.url)
+            .query(params)
+            .header(USER_AGENT, self.client.agent.header_value())
     }
 
     /// Wraps `query` in a [`SparqlQuery`] ready to be executed against this endpoint.
@@ -156,6 +627,39 @@ impl Endpoint {
     {
         query.build(self)
     }
+
+    /// Sends `update` to the update URL as an `update=` form parameter.
+    ///
+    /// Resolves to an [`UpdateResponse`] when the endpoint answers with a `2xx`
+    /// status, and to [`UpdateError::Status`] with the endpoint's error
+    /// message otherwise.
+    ///
+    /// ```no_run
+    /// use sparql_http_client::{Endpoint, SparqlClient, UpdateString};
+    ///
+    /// # #[tokio::main] async fn main() -> anyhow::Result<()> {
+    /// let endpoint = Endpoint::new(SparqlClient::default(), "http://localhost:3030/ds/query")
+    ///     .with_update_url("http://localhost:3030/ds/update");
+    /// let us: UpdateString = "CLEAR GRAPH <http://example.org/g>".parse()?;
+    ///
+    /// endpoint.update(us).await?;
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// Prefer the [`update!`](crate::update!) macro for compile-time validation.
+    pub async fn update(&self, update: UpdateString) -> Result<UpdateResponse, UpdateError> {
+        let request = self
+            .post(self.update_url.as_deref().unwrap_or(&self.url))
+            .form(&[("update", &*update)]);
+        let _in_flight = self.acquire().await;
+        self.wait_for_window().await;
+        let response = self.authorize(request).await?.send().await?;
+        let response = StatusError::check::<UpdateError>(response).await?;
+
+        let status = response.status();
+        let body = response.text().await?.into_boxed_str();
+        Ok(UpdateResponse { status, body })
+    }
 }
 
 #[cfg(test)]
@@ -166,4 +670,77 @@ mod tests {
     fn default_client() {
         SparqlClient::default();
     }
+
+    #[test]
+    fn builder() {
+        let client = SparqlClient::builder()
+            .with_connect_timeout(Duration::from_secs(1))
+            .with_read_timeout(Duration::from_secs(1))
+            .with_proxy(reqwest::Proxy::http("http://localhost:3128").unwrap())
+            .with_pool_max_idle_per_host(0)
+            .with_auth(Auth::bearer("t0k"))
+            .build()
+            .unwrap();
+        assert!(client.auth.is_some());
+    }
+
+    fn build(endpoint: &Endpoint, method: QueryMethod, query: &str) -> reqwest::Request {
+        let params = [
+            ("query", query),
+            ("default-graph-uri", "http://example.org/g"),
+        ];
+        endpoint
+            .query_request(method, &params, HeaderValue::from_static("text/csv"))
+            .build()
+            .unwrap()
+    }
+
+    fn body(request: &reqwest::Request) -> &str {
+        std::str::from_utf8(request.body().unwrap().as_bytes().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn query_methods() {
+        let endpoint = Endpoint::new(SparqlClient::default(), "http://localhost/sparql");
+        let query = "ASK { ?s ?p \"&\" }";
+
+        let get = build(&endpoint, QueryMethod::Get, query);
+        assert_eq!(get.method(), "GET");
+        assert_eq!(
+            get.url().as_str(),
+            "http://localhost/sparql?query=ASK+%7B+%3Fs+%3Fp+%22%26%22+%7D\
+             &default-graph-uri=http%3A%2F%2Fexample.org%2Fg"
+        );
+        assert!(get.body().is_none());
+        assert_eq!(get.headers()[ACCEPT], "text/csv");
+
+        let form = build(&endpoint, QueryMethod::UrlEncodedPost, query);
+        assert_eq!(form.method(), "POST");
+        assert_eq!(form.url().query(), None);
+        assert_eq!(body(&form), get.url().query().unwrap());
+
+        let direct = build(&endpoint, QueryMethod::DirectPost, query);
+        assert_eq!(direct.method(), "POST");
+        assert_eq!(direct.headers()[CONTENT_TYPE], "application/sparql-query");
+        assert_eq!(
+            direct.url().query(),
+            Some("default-graph-uri=http%3A%2F%2Fexample.org%2Fg")
+        );
+        assert_eq!(body(&direct), query);
+    }
+
+    #[test]
+    fn auto_method() {
+        let endpoint = Endpoint::new(SparqlClient::default(), "http://localhost/sparql");
+        let auto = QueryMethod::Auto {
+            max_url_length: 120,
+        };
+
+        let short = build(&endpoint, auto, "ASK {}");
+        assert_eq!(short.method(), "GET");
+        assert!(short.url().as_str().len() <= 120);
+
+        let long = build(&endpoint, auto, &format!("ASK {{}} # {}", "x".repeat(64)));
+        assert_eq!(long.method(), "POST");
+    }
 }
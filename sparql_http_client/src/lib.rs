@@ -1,16 +1,39 @@
 #![doc = include_str!("../README.md")]
 
+pub mod auth;
 pub mod client;
 pub mod query;
+pub mod rate_limit;
 pub mod response;
+pub mod retry;
 
-pub use client::{Endpoint, SparqlClient, UserAgent};
+pub use auth::Auth;
+pub use client::{Endpoint, QueryMethod, SparqlClient, SparqlClientBuilder, UserAgent};
+/// The RDF model [`response::RDFTerm`] converts to and from, re-exported so
+/// the conversions are used with a matching version.
+pub use oxrdf;
 pub use query::{
-    AskQuery, AskQueryString, QueryString, QueryStringError, QueryType, SelectQuery,
-    SelectQueryString, SparqlQuery,
+    AskQuery, AskQueryString, BindError, ConstructQuery, ConstructQueryString, DescribeQuery,
+    DescribeQueryString, PreparedQuery, QueryString, QueryStringError, QueryType, SelectQuery,
+    SelectQueryString, SparqlQuery, TypedSelectQuery, UpdateString,
 };
-pub use response::{AskQueryResponse, ParseError, Row, SelectQueryResponse, StreamError};
-pub use sparql_http_client_macros::query;
+pub use rate_limit::RateLimit;
+/// The HTTP client this crate is built on, re-exported for configuring
+/// [`SparqlClientBuilder`] with proxies and certificates.
+pub use reqwest;
+pub use response::{
+    AskQueryResponse, ConstructQueryResponse, DescribeQueryResponse, DescribedResource, FromRow,
+    ParseError, ResultFormat, Row, RowError, SelectQueryResponse, StatusError, StreamError, Triple,
+    UpdateError, UpdateResponse,
+};
+pub use retry::RetryPolicy;
+pub use sparql_http_client_macros::{query, query_as, query_file, update, FromRow};
+
+/// Support items for code generated by the macros. Not public API.
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::response::row::{assert_projected, optional, required};
+}
 
 #[cfg(test)]
 mod tests {
@@ -1,24 +1,43 @@
 pub mod ask;
+pub mod construct;
+pub mod describe;
 pub mod error;
+pub mod prepared;
 pub mod select;
+pub mod update;
 
 pub use ask::AskQueryString;
-pub use error::QueryStringError;
+pub use construct::ConstructQueryString;
+pub use describe::DescribeQueryString;
+pub use error::{BindError, QueryStringError};
+pub use prepared::PreparedQuery;
 pub use select::SelectQueryString;
+pub use update::UpdateString;
 
 use std::fmt;
+use std::marker::PhantomData;
 use std::ops::Deref;
 use std::str::FromStr;
 
+use futures_util::Stream;
+use reqwest::header::HeaderValue;
 use spargebra::Query;
 
-use crate::client::Endpoint;
-use crate::response::{AskQueryResponse, SelectQueryResponse, StreamError};
+use crate::client::{Dataset, Endpoint, QueryMethod};
+use crate::rate_limit;
+use crate::response::{
+    AskQueryResponse, ConstructQueryResponse, DescribeQueryResponse, FromRow, ResultFormat, Row,
+    SelectQueryResponse, StatusError, StreamError,
+};
+
+/// The `Accept` header of CONSTRUCT and DESCRIBE queries.
+const RDF_ACCEPT: &str = "application/n-triples, text/turtle;q=0.9";
 
 /// An owned, validated, normalized SPARQL query string.
 ///
-/// Implementors hold the query text. The two built-in implementations are
-/// [`SelectQueryString`] and [`AskQueryString`].
+/// Implementors hold the query text. The built-in implementations are
+/// [`SelectQueryString`], [`AskQueryString`], [`ConstructQueryString`] and
+/// [`DescribeQueryString`].
 ///
 /// Obtain an instance by parsing at runtime with [`str::parse`]:
 ///
@@ -43,6 +62,10 @@ pub trait QueryString:
         SparqlQuery {
             endpoint,
             query: self,
+            result_format: None,
+            query_method: None,
+            dataset: Dataset::default(),
+            row: PhantomData,
         }
     }
 }
@@ -53,41 +76,375 @@ pub trait QueryString:
 ///
 /// - `SparqlQuery<SelectQueryString>`: call [`run`](SparqlQuery::run)
 /// - `SparqlQuery<AskQueryString>`: call [`run`](SparqlQuery::run)
+/// - `SparqlQuery<ConstructQueryString>`: call [`run`](SparqlQuery::run)
+/// - `SparqlQuery<DescribeQueryString>`: call [`run`](SparqlQuery::run)
+///
+/// See also the type aliases [`SelectQuery`], [`AskQuery`], [`ConstructQuery`]
+/// and [`DescribeQuery`].
 ///
-/// See also the type aliases [`SelectQuery`] and [`AskQuery`].
-#[derive(Debug)]
-pub struct SparqlQuery<Q> {
+/// `R` is the type SELECT result rows are yielded as. It is [`Row`] unless
+/// changed with [`with_row_type`](SparqlQuery::with_row_type), which the
+/// [`query!`](crate::query!) macro does with a struct generated from the
+/// projection.
+pub struct SparqlQuery<Q, R = Row> {
     endpoint: Endpoint,
     query: Q,
+    result_format: Option<ResultFormat>,
+    query_method: Option<QueryMethod>,
+    dataset: Dataset,
+    row: PhantomData<fn() -> R>,
+}
+
+impl<Q: fmt::Debug, R> fmt::Debug for SparqlQuery<Q, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SparqlQuery")
+            .field("endpoint", &self.endpoint)
+            .field("query", &self.query)
+            .field("result_format", &self.result_format)
+            .field("query_method", &self.query_method)
+            .field("dataset", &self.dataset)
+            .finish()
+    }
+}
+
+impl<Q: QueryString, R> SparqlQuery<Q, R> {
+    /// Adds `iri` to the default graph of this query, with the
+    /// `default-graph-uri` protocol parameter.
+    ///
+    /// Setting a default or named graph on the query replaces the dataset
+    /// configured on the endpoint with
+    /// [`Endpoint::with_default_graph`] and [`Endpoint::with_named_graph`].
+    /// Per the SPARQL protocol, the request dataset also takes precedence over
+    /// `FROM` and `FROM NAMED` clauses in the query.
+    ///
+    /// ```
+    /// use sparql_http_client::{query, Endpoint, SparqlClient};
+    ///
+    /// let endpoint = Endpoint::new(SparqlClient::default(), "http://localhost:3030/ds/query");
+    /// let query = query!(endpoint, "SELECT ?g ?s WHERE { GRAPH ?g { ?s ?p ?o } }")
+    ///     .with_named_graph("http://example.org/tenants/acme/2024")
+    ///     .with_named_graph("http://example.org/tenants/acme/2025");
+    /// ```
+    pub fn with_default_graph(mut self, iri: &str) -> Self {
+        self.dataset.add_default_graph(iri);
+        self
+    }
+
+    /// Adds `iri` to the named graphs of this query, with the
+    /// `named-graph-uri` protocol parameter.
+    ///
+    /// See [`with_default_graph`](SparqlQuery::with_default_graph).
+    pub fn with_named_graph(mut self, iri: &str) -> Self {
+        self.dataset.add_named_graph(iri);
+        self
+    }
+
+    /// Sets how this query is sent, overriding the endpoint's
+    /// [`with_query_method`](Endpoint::with_query_method) setting.
+    ///
+    /// ```
+    /// use sparql_http_client::{query, Endpoint, QueryMethod, SparqlClient};
+    ///
+    /// let endpoint = Endpoint::new(SparqlClient::default(), "https://example.org/sparql");
+    /// let query = query!(endpoint, "ASK { ?s ?p ?o }").with_query_method(QueryMethod::Get);
+    /// ```
+    pub fn with_query_method(mut self, method: QueryMethod) -> Self {
+        self.query_method = Some(method);
+        self
+    }
+
+    /// Sends this query, asking for `accept`, and checks the response status.
+    ///
+    /// Transient failures are retried according to the endpoint's
+    /// [`RetryPolicy`](crate::RetryPolicy), until a response with a success
+    /// status is returned.
+    async fn send(&self, accept: HeaderValue) -> Result<reqwest::Response, StreamError> {
+        let method = self
+            .query_method
+            .unwrap_or_else(|| self.endpoint.query_method());
+        let params = self.params();
+        let mut retries = self.endpoint.retry_policy().map(|policy| policy.start());
+        let permit = self.endpoint.acquire().await;
+        loop {
+            self.endpoint.wait_for_window().await;
+            let request = self.endpoint.query_request(method, &params, accept.clone());
+            let result = match self.endpoint.authorize(request).await?.send().await {
+                Ok(response) => StatusError::check::<StreamError>(response).await,
+                Err(e) => Err(e.into()),
+            };
+            let delay = match (&result, &mut retries) {
+                (Err(e), Some(retries)) => retries.delay(e),
+                _ => None,
+            };
+            match delay {
+                Some(delay) => tokio::time::sleep(delay).await,
+                None => {
+                    return match permit {
+                        Some(permit) => result.map(|r| rate_limit::hold(r, permit)),
+                        None => result,
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns the protocol parameters of the request: the query and its dataset.
+    fn params(&self) -> Vec<(&'static str, &str)> {
+        let dataset = if self.dataset.is_empty() {
+            self.endpoint.dataset()
+        } else {
+            &self.dataset
+        };
+        let mut params = vec![("query", &*self.query)];
+        params.extend(dataset.params());
+        params
+    }
 }
 
 impl SparqlQuery<AskQueryString> {
+    /// Requests results in `format` for this query only, overriding the
+    /// endpoint's [`with_result_format`](Endpoint::with_result_format) setting.
+    ///
+    /// [`ResultFormat::Tsv`] and [`ResultFormat::Csv`] cannot encode a boolean,
+    /// so JSON is preferred instead.
+    pub fn with_result_format(mut self, format: ResultFormat) -> Self {
+        self.result_format = Some(format);
+        self
+    }
+
     /// Sends the ASK query to the endpoint and deserializes the response.
-    pub async fn run(self) -> Result<AskQueryResponse, reqwest::Error> {
-        self.endpoint
-            .request()
-            .form(&[("query", &*self.query)])
-            .send()
-            .await?
-            .json::<AskQueryResponse>()
-            .await
+    ///
+    /// A non-`2xx` answer is returned as [`StreamError::Status`].
+    pub async fn run(self) -> Result<AskQueryResponse, StreamError> {
+        let format = match self
+            .result_format
+            .unwrap_or_else(|| self.endpoint.result_format())
+        {
+            ResultFormat::Tsv | ResultFormat::Csv => ResultFormat::Json,
+            format => format,
+        };
+        let response = self.send(format.ask_accept()).await?;
+        AskQueryResponse::from_response(response, format).await
     }
 }
 
-impl SparqlQuery<SelectQueryString> {
+impl<R: From<Row>> SparqlQuery<SelectQueryString, R> {
+    /// Requests results in `format` for this query only, overriding the
+    /// endpoint's [`with_result_format`](Endpoint::with_result_format) setting.
+    pub fn with_result_format(mut self, format: ResultFormat) -> Self {
+        self.result_format = Some(format);
+        self
+    }
+
+    /// Changes the type [`SelectQueryResponse::into_rows`] yields rows as.
+    ///
+    /// [`query!`](crate::query!) sets it to a struct with an
+    /// `Option<RDFTerm>` field per projected variable, so that a misspelt
+    /// variable does not compile:
+    ///
+    /// ```compile_fail
+    /// use futures_util::TryStreamExt;
+    /// use sparql_http_client::{query, Endpoint, SparqlClient};
+    ///
+    /// # async fn run() -> anyhow::Result<()> {
+    /// let endpoint = Endpoint::new(SparqlClient::default(), "https://example.org/sparql");
+    /// let rows = query!(endpoint, "SELECT ?countryLabel WHERE { ?c ?p ?countryLabel }")
+    ///     .run()
+    ///     .await?
+    ///     .collect()
+    ///     .await?;
+    /// println!("{:?}", rows[0].countrylabel);
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// The struct is declared in a block of its own, after the endpoint and the
+    /// bound values are evaluated, so it never shadows the caller's items, even
+    /// one named `Row`:
+    ///
+    /// ```no_run
+    /// use sparql_http_client::response::RDFTerm;
+    /// use sparql_http_client::{query, Endpoint, SparqlClient};
+    ///
+    /// #[derive(Default)]
+    /// struct Row {
+    ///     id: &'static str,
+    /// }
+    ///
+    /// # fn run() -> Result<(), sparql_http_client::BindError> {
+    /// let endpoint = Endpoint::new(SparqlClient::default(), "https://example.org/sparql");
+    /// let query = query!(
+    ///     endpoint,
+    ///     "SELECT ?s WHERE { ?s ?p $o }",
+    ///     o = RDFTerm::iri(Row::default().id),
+    /// )?;
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// Calling it with [`Row`] turns those rows back into maps, for instance
+    /// to name the query's type as [`SelectQuery`]:
+    ///
+    /// ```no_run
+    /// use sparql_http_client::{query, Endpoint, Row, SelectQuery, SparqlClient};
+    ///
+    /// # async fn run() -> anyhow::Result<()> {
+    /// let endpoint = Endpoint::new(SparqlClient::default(), "https://example.org/sparql");
+    /// let typed = query!(endpoint.clone(), "SELECT ?type ?1st WHERE { ?1st a ?type }");
+    /// for row in typed.run().await?.collect().await? {
+    ///     println!("{:?} {:?}", row.r#type, row._1st);
+    /// }
+    ///
+    /// let untyped: SelectQuery = query!(endpoint, "SELECT ?type WHERE { ?s a ?type }")
+    ///     .with_row_type::<Row>();
+    /// # Ok(()) }
+    /// ```
+    pub fn with_row_type<T: From<Row>>(self) -> SparqlQuery<SelectQueryString, T> {
+        SparqlQuery {
+            endpoint: self.endpoint,
+            query: self.query,
+            result_format: self.result_format,
+            query_method: self.query_method,
+            dataset: self.dataset,
+            row: PhantomData,
+        }
+    }
+
     /// Sends the query and streams result rows as they arrive over the network.
     ///
-    /// The endpoint is asked for `text/tab-separated-values`; the
-    /// [`vars`](SelectQueryResponse::vars) field is populated from the first line,
-    /// then rows are yielded one at a time via [`SelectQueryResponse::into_rows`].
-    pub async fn run(self) -> Result<SelectQueryResponse, StreamError> {
-        let response = self
-            .endpoint
-            .request_tsv()
-            .form(&[("query", &*self.query)])
-            .send()
+    /// The endpoint is asked for the configured [`ResultFormat`] (TSV unless
+    /// overridden) with every other supported format as a fallback, and the
+    /// response is parsed according to its `Content-Type`. The
+    /// [`vars`](SelectQueryResponse::vars) field is populated from the response
+    /// header, then rows are yielded one at a time via
+    /// [`SelectQueryResponse::into_rows`].
+    ///
+    /// A non-`2xx` answer is returned as [`StreamError::Status`] instead of being
+    /// parsed as results, and an unsupported `Content-Type` as
+    /// [`StreamError::UnsupportedContentType`].
+    pub async fn run(self) -> Result<SelectQueryResponse<R>, StreamError> {
+        let format = self
+            .result_format
+            .unwrap_or_else(|| self.endpoint.result_format());
+        let response = self.send(format.select_accept()).await?;
+        let response = SelectQueryResponse::from_response(response, format).await?;
+        Ok(response.with_row_type())
+    }
+}
+
+impl<R: From<Row>> SparqlQuery<SelectQueryString, R> {
+    /// Converts every result row into `T` with its [`FromRow`] implementation.
+    ///
+    /// Unlike [`query_as!`](crate::query_as!), this does not check that the
+    /// projection binds the variables `T` requires; missing variables surface
+    /// as row errors at runtime.
+    pub fn rows_as<T: FromRow>(self) -> TypedSelectQuery<T> {
+        TypedSelectQuery {
+            query: self.with_row_type(),
+            row: PhantomData,
+        }
+    }
+}
+
+/// A SELECT query whose rows are converted into `T`.
+///
+/// Created by the [`query_as!`](crate::query_as!) macro or
+/// [`SparqlQuery::rows_as`].
+///
+/// ```no_run
+/// use futures_util::TryStreamExt;
+/// use sparql_http_client::{Endpoint, FromRow, SparqlClient, query_as};
+///
+/// #[derive(FromRow)]
+/// struct Label {
+///     s: String,
+///     #[sparql(var = "label")]
+///     text: Option<String>,
+/// }
+///
+/// # #[tokio::main] async fn main() -> anyhow::Result<()> {
+/// let endpoint = Endpoint::new(SparqlClient::default(), "https://example.org/sparql");
+/// let labels: Vec<Label> = query_as!(Label, endpoint, r#"
+///     SELECT ?s ?label WHERE {
+///         ?s ?p ?o OPTIONAL { ?s <http://www.w3.org/2000/01/rdf-schema#label> ?label }
+///     }
+/// "#)
+/// .run()
+/// .await?
+/// .try_collect()
+/// .await?;
+/// # Ok(()) }
+/// ```
+pub struct TypedSelectQuery<T> {
+    query: SelectQuery,
+    row: PhantomData<fn() -> T>,
+}
+
+impl<T: FromRow> TypedSelectQuery<T> {
+    /// Requests results in `format` for this query only, see
+    /// [`SparqlQuery::with_result_format`].
+    pub fn with_result_format(mut self, format: ResultFormat) -> Self {
+        self.query = self.query.with_result_format(format);
+        self
+    }
+
+    /// Sets how this query is sent, see [`SparqlQuery::with_query_method`].
+    pub fn with_query_method(mut self, method: QueryMethod) -> Self {
+        self.query = self.query.with_query_method(method);
+        self
+    }
+
+    /// Adds `iri` to the default graph of this query, see
+    /// [`SparqlQuery::with_default_graph`].
+    pub fn with_default_graph(mut self, iri: &str) -> Self {
+        self.query = self.query.with_default_graph(iri);
+        self
+    }
+
+    /// Adds `iri` to the named graphs of this query, see
+    /// [`SparqlQuery::with_named_graph`].
+    pub fn with_named_graph(mut self, iri: &str) -> Self {
+        self.query = self.query.with_named_graph(iri);
+        self
+    }
+
+    /// Sends the query and streams the converted rows as they arrive over the network.
+    ///
+    /// See [`SelectQueryResponse::into_rows_as`].
+    pub async fn run(self) -> Result<impl Stream<Item = Result<T, StreamError>>, StreamError> {
+        Ok(self.query.run().await?.into_rows_as())
+    }
+}
+
+impl<T> fmt::Debug for TypedSelectQuery<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TypedSelectQuery")
+            .field("query", &self.query)
+            .finish()
+    }
+}
+
+impl SparqlQuery<ConstructQueryString> {
+    /// Sends the query and streams the resulting triples as they arrive over the network.
+    ///
+    /// The endpoint is asked for `application/n-triples`, with `text/turtle` as a
+    /// fallback; triples are yielded one at a time via
+    /// [`ConstructQueryResponse::into_triples`].
+    pub async fn run(self) -> Result<ConstructQueryResponse, StreamError> {
+        let response = self.send(HeaderValue::from_static(RDF_ACCEPT)).await?;
+        ConstructQueryResponse::from_response(response)
+    }
+}
+
+impl SparqlQuery<DescribeQueryString> {
+    /// Sends the query and groups the returned triples by described resource.
+    ///
+    /// Content negotiation is the same as for CONSTRUCT queries; the whole
+    /// graph is received before [`DescribeQueryResponse`] is returned.
+    pub async fn run(self) -> Result<DescribeQueryResponse, StreamError> {
+        let response = self.send(HeaderValue::from_static(RDF_ACCEPT)).await?;
+        let triples = ConstructQueryResponse::from_response(response)?
+            .collect()
             .await?;
-        SelectQueryResponse::from_response(response).await
+        Ok(DescribeQueryResponse::from_triples(triples))
     }
 }
 
@@ -103,6 +460,18 @@ pub type SelectQuery = SparqlQuery<SelectQueryString>;
 /// [`Endpoint::build_query`] with an [`AskQueryString`].
 pub type AskQuery = SparqlQuery<AskQueryString>;
 
+/// Type alias for a CONSTRUCT query bound to an endpoint.
+///
+/// Returned by [`query!`](crate::query!) for `CONSTRUCT` statements, or by
+/// [`Endpoint::build_query`] with a [`ConstructQueryString`].
+pub type ConstructQuery = SparqlQuery<ConstructQueryString>;
+
+/// Type alias for a DESCRIBE query bound to an endpoint.
+///
+/// Returned by [`query!`](crate::query!) for `DESCRIBE` statements, or by
+/// [`Endpoint::build_query`] with a [`DescribeQueryString`].
+pub type DescribeQuery = SparqlQuery<DescribeQueryString>;
+
 /// The kind of a SPARQL query.
 ///
 /// Used in [`QueryStringError::WrongKind`] to describe a mismatch between the
@@ -111,9 +480,9 @@ pub type AskQuery = SparqlQuery<AskQueryString>;
 pub enum QueryType {
     /// A `SELECT` query, returning a table of variable bindings.
     Select,
-    /// A `CONSTRUCT` query, returning an RDF graph. Not currently supported.
+    /// A `CONSTRUCT` query, returning an RDF graph.
     Construct,
-    /// A `DESCRIBE` query, returning an RDF graph. Not currently supported.
+    /// A `DESCRIBE` query, returning an RDF graph.
     Describe,
     /// An `ASK` query, returning a boolean.
     Ask,
@@ -140,3 +509,38 @@ impl fmt::Display for QueryType {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SparqlClient;
+
+    #[test]
+    fn dataset_params() {
+        let endpoint = Endpoint::new(SparqlClient::default(), "http://localhost/sparql")
+            .with_default_graph("http://example.org/a")
+            .with_named_graph("http://example.org/b");
+        let qs: AskQueryString = "ASK { ?s ?p ?o }".parse().unwrap();
+
+        let query = endpoint.clone().build_query(qs.clone());
+        assert_eq!(
+            query.params()[1..],
+            [
+                ("default-graph-uri", "http://example.org/a"),
+                ("named-graph-uri", "http://example.org/b"),
+            ]
+        );
+
+        let query = endpoint
+            .build_query(qs)
+            .with_default_graph("http://example.org/c")
+            .with_default_graph("http://example.org/d");
+        assert_eq!(
+            query.params()[1..],
+            [
+                ("default-graph-uri", "http://example.org/c"),
+                ("default-graph-uri", "http://example.org/d"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,112 @@
+use std::fmt;
+use std::ops::Deref;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use spargebra::SparqlParser;
+
+use super::{QueryString, QueryStringError, QueryType};
+
+/// An owned, validated, normalized CONSTRUCT query string.
+///
+/// Parse from a `&str` at runtime via [`str::parse`]:
+///
+/// ```
+/// use sparql_http_client::ConstructQueryString;
+///
+/// let qs: ConstructQueryString =
+///     "CONSTRUCT { ?s ?p ?o } WHERE { ?s ?p ?o }".parse().unwrap();
+/// ```
+///
+/// Passing the wrong query kind returns a [`QueryStringError::WrongKind`]:
+///
+/// ```
+/// use sparql_http_client::ConstructQueryString;
+///
+/// let result = "SELECT ?s WHERE { ?s ?p ?o }".parse::<ConstructQueryString>();
+/// assert!(result.is_err());
+/// ```
+///
+/// [`Deref`] and [`Display`](std::fmt::Display) both yield the normalised query string.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ConstructQueryString(Arc<str>);
+
+impl FromStr for ConstructQueryString {
+    type Err = QueryStringError;
+
+    fn from_str(s: &str) -> Result<Self, QueryStringError> {
+        let q = SparqlParser::new().parse_query(s)?;
+        match QueryType::from(&q) {
+            QueryType::Construct => Ok(Self(q.to_string().into())),
+            provided => Err(QueryStringError::WrongKind {
+                expected: QueryType::Construct,
+                provided,
+            }),
+        }
+    }
+}
+
+impl Deref for ConstructQueryString {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for ConstructQueryString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self)
+    }
+}
+
+impl QueryString for ConstructQueryString {
+    fn new_unchecked(s: &str) -> Self {
+        Self(Arc::from(s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::{Endpoint, SparqlClient};
+
+    const WIKIDATA: &str = "https://query.wikidata.org/bigdata/namespace/wdq/sparql";
+
+    const QUERY: &str = r#"
+        PREFIX wd: <http://www.wikidata.org/entity/>
+
+        CONSTRUCT { wd:Q243 ?pred ?obj } WHERE {
+            wd:Q243 ?pred ?obj .
+        } LIMIT 3
+    "#;
+
+    #[test]
+    fn wrong_kind() {
+        let err = "ASK { ?s ?p ?o }"
+            .parse::<ConstructQueryString>()
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            QueryStringError::WrongKind {
+                expected: QueryType::Construct,
+                provided: QueryType::Ask,
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn run_collect() -> anyhow::Result<()> {
+        let qs: ConstructQueryString = QUERY.parse()?;
+        let triples = Endpoint::new(SparqlClient::default(), WIKIDATA)
+            .build_query(qs)
+            .run()
+            .await?
+            .collect()
+            .await?;
+
+        assert_eq!(triples.len(), 3);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,110 @@
+use std::fmt;
+use std::ops::Deref;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use spargebra::SparqlParser;
+
+use super::{QueryString, QueryStringError, QueryType};
+
+/// An owned, validated, normalized DESCRIBE query string.
+///
+/// Parse from a `&str` at runtime via [`str::parse`]:
+///
+/// ```
+/// use sparql_http_client::DescribeQueryString;
+///
+/// let qs: DescribeQueryString =
+///     "DESCRIBE <http://example.org/alice>".parse().unwrap();
+/// ```
+///
+/// Passing the wrong query kind returns a [`QueryStringError::WrongKind`]:
+///
+/// ```
+/// use sparql_http_client::DescribeQueryString;
+///
+/// let result = "SELECT ?s WHERE { ?s ?p ?o }".parse::<DescribeQueryString>();
+/// assert!(result.is_err());
+/// ```
+///
+/// [`Deref`] and [`Display`](std::fmt::Display) both yield the normalised query string.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DescribeQueryString(Arc<str>);
+
+impl FromStr for DescribeQueryString {
+    type Err = QueryStringError;
+
+    fn from_str(s: &str) -> Result<Self, QueryStringError> {
+        let q = SparqlParser::new().parse_query(s)?;
+        match QueryType::from(&q) {
+            QueryType::Describe => Ok(Self(q.to_string().into())),
+            provided => Err(QueryStringError::WrongKind {
+                expected: QueryType::Describe,
+                provided,
+            }),
+        }
+    }
+}
+
+impl Deref for DescribeQueryString {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for DescribeQueryString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self)
+    }
+}
+
+impl QueryString for DescribeQueryString {
+    fn new_unchecked(s: &str) -> Self {
+        Self(Arc::from(s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::{Endpoint, SparqlClient};
+
+    const WIKIDATA: &str = "https://query.wikidata.org/bigdata/namespace/wdq/sparql";
+
+    const QUERY: &str = r#"
+        PREFIX wd: <http://www.wikidata.org/entity/>
+
+        DESCRIBE wd:Q243
+    "#;
+
+    #[test]
+    fn wrong_kind() {
+        let err = "ASK { ?s ?p ?o }"
+            .parse::<DescribeQueryString>()
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            QueryStringError::WrongKind {
+                expected: QueryType::Describe,
+                provided: QueryType::Ask,
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn run() -> anyhow::Result<()> {
+        let qs: DescribeQueryString = QUERY.parse()?;
+        let response = Endpoint::new(SparqlClient::default(), WIKIDATA)
+            .build_query(qs)
+            .run()
+            .await?;
+
+        assert!(response
+            .get("http://www.wikidata.org/entity/Q243")
+            .is_some_and(|resource| !resource.triples.is_empty()));
+
+        Ok(())
+    }
+}
@@ -4,8 +4,10 @@ use super::QueryType;
 
 /// An error returned when parsing a SPARQL query string fails.
 ///
-/// Produced by [`SelectQueryString`](crate::SelectQueryString) and
-/// [`AskQueryString`](crate::AskQueryString) when parsing via [`str::parse`].
+/// Produced by [`SelectQueryString`](crate::SelectQueryString),
+/// [`AskQueryString`](crate::AskQueryString),
+/// [`ConstructQueryString`](crate::ConstructQueryString) and
+/// [`DescribeQueryString`](crate::DescribeQueryString) when parsing via [`str::parse`].
 ///
 /// # Example
 ///
@@ -36,3 +38,50 @@ pub enum QueryStringError {
         provided: QueryType,
     },
 }
+
+/// An error returned when binding values to a [`PreparedQuery`](crate::PreparedQuery) fails.
+///
+/// # Example
+///
+/// ```
+/// use sparql_http_client::{BindError, PreparedQuery, SelectQueryString};
+///
+/// let err = "SELECT ?label WHERE { $item <http://www.w3.org/2000/01/rdf-schema#label> ?label }"
+///     .parse::<PreparedQuery<SelectQueryString>>()
+///     .unwrap()
+///     .finish()
+///     .unwrap_err();
+///
+/// assert!(matches!(err, BindError::Unbound(name) if &*name == "item"));
+/// ```
+#[derive(Debug, thiserror::Error)]
+pub enum BindError {
+    /// A `$` placeholder of the query was never bound.
+    #[error("placeholder `${0}` is not bound")]
+    Unbound(Box<str>),
+
+    /// A value was bound to a name that is not a variable of the query.
+    #[error("`{0}` is not a variable of the query")]
+    UnknownVariable(Box<str>),
+
+    /// The variable is the target of a `BIND`, an aggregate or a `SELECT`
+    /// expression, so it cannot also be given a value.
+    #[error("`{0}` is assigned by the query and cannot be bound")]
+    Assigned(Box<str>),
+
+    /// The variable is used as a predicate, graph or service name, which only an IRI can fill.
+    #[error("`{0}` must be bound to an IRI")]
+    NotAnIri(Box<str>),
+
+    /// The bound term is not valid in a query, e.g. a blank node or a malformed IRI.
+    #[error("invalid value for `{var}`: {reason}")]
+    InvalidTerm { var: Box<str>, reason: Box<str> },
+
+    /// The variable occurs in a construct this crate cannot substitute into.
+    #[error("cannot substitute `{0}` in this query")]
+    Unsupported(Box<str>),
+
+    /// The query produced by the substitution failed to parse back.
+    #[error(transparent)]
+    Query(#[from] QueryStringError),
+}
@@ -0,0 +1,685 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use spargebra::algebra::{AggregateExpression, Expression, GraphPattern, OrderExpression};
+use spargebra::term::{
+    GroundTerm, Literal, NamedNode, NamedNodePattern, TermPattern, TriplePattern, Variable,
+};
+use spargebra::{Query, SparqlParser};
+
+use super::{BindError, QueryString, QueryStringError};
+use crate::response::{LiteralType, RDFTerm, RDFType};
+
+/// A query with placeholders that are filled in with [`bind`](PreparedQuery::bind).
+///
+/// Values are substituted into the parsed query algebra, never spliced into the
+/// query text, so a bound string always stays a single literal however many
+/// quotes or braces it contains. Every occurrence of the bound variable is
+/// replaced, including inside `FILTER`s, `VALUES` blocks and subqueries, and a
+/// projected variable is kept in the results as a constant.
+///
+/// Variables are matched by name across the whole query. A sub-`SELECT` that
+/// does not project a variable has its own, separate variable of that name in
+/// SPARQL, but it is replaced all the same; rename the inner variable to keep
+/// it unbound.
+///
+/// Parsing from a string with [`str::parse`] records the `$name` variables as
+/// placeholders that must all be bound before [`finish`](PreparedQuery::finish);
+/// `?name` variables may be bound too, but are optional. A query string that was
+/// already parsed, such as a [`SelectQueryString`](crate::SelectQueryString),
+/// converts with [`From`] and has no required placeholders.
+///
+/// ```
+/// use sparql_http_client::{PreparedQuery, SelectQueryString};
+/// use sparql_http_client::response::RDFTerm;
+///
+/// let query: SelectQueryString = r#"
+///     SELECT ?label WHERE {
+///         $item <http://www.w3.org/2000/01/rdf-schema#label> ?label
+///         FILTER(LANG(?label) = $lang)
+///     }
+/// "#
+/// .parse::<PreparedQuery<SelectQueryString>>()?
+/// .bind("item", RDFTerm::iri("http://www.wikidata.org/entity/Q142"))
+/// .bind("lang", "en\" || true || \"")
+/// .finish()?;
+///
+/// assert!(query.contains("<http://www.wikidata.org/entity/Q142>"));
+/// assert!(query.contains(r#""en\" || true || \"""#));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// The [`query!`](crate::query!) macro accepts bindings as `name = value`
+/// arguments and checks at compile time that every placeholder is bound:
+///
+/// ```compile_fail
+/// use sparql_http_client::{query, Endpoint, SparqlClient};
+///
+/// let endpoint = Endpoint::new(SparqlClient::default(), "https://example.org/sparql");
+/// let query = query!(endpoint, "SELECT ?s WHERE { ?s ?p $o }", p = "unused");
+/// ```
+pub struct PreparedQuery<Q> {
+    query: Query,
+    placeholders: Box<[Box<str>]>,
+    bindings: HashMap<Box<str>, GroundTerm>,
+    error: Option<BindError>,
+    kind: PhantomData<Q>,
+}
+
+impl<Q: QueryString> PreparedQuery<Q> {
+    #[doc(hidden)]
+    pub fn new_unchecked(s: &str, placeholders: &[&str]) -> Self {
+        Self {
+            query: SparqlParser::new()
+                .parse_query(s)
+                .expect("query validated at compile time"),
+            placeholders: placeholders.iter().map(|&p| p.into()).collect(),
+            bindings: HashMap::new(),
+            error: None,
+            kind: PhantomData,
+        }
+    }
+
+    /// Returns the names of the `$` placeholders that must be bound.
+    pub fn placeholders(&self) -> &[Box<str>] {
+        &self.placeholders
+    }
+
+    /// Binds `value` to the variable `name`, given without its `$` or `?` sigil.
+    ///
+    /// Anything convertible into an [`RDFTerm`] can be bound: `&str` and
+    /// `String` become plain literals, integers, floats and `bool` become
+    /// XSD-typed literals, and [`RDFTerm::iri`] or [`RDFTerm::lang_literal`]
+    /// build other terms. Binding the same name again replaces the value.
+    ///
+    /// Errors are reported by [`finish`](PreparedQuery::finish), so calls can be chained.
+    pub fn bind(mut self, name: &str, value: impl Into<RDFTerm>) -> Self {
+        if self.error.is_none() {
+            match ground_term(name, value.into()) {
+                Ok(term) => {
+                    self.bindings.insert(name.into(), term);
+                }
+                Err(e) => self.error = Some(e),
+            }
+        }
+        self
+    }
+
+    /// Substitutes the bound values and returns the resulting query string.
+    ///
+    /// Fails if a `$` placeholder is unbound, a bound name is not a variable of
+    /// the query, or a value does not fit where the variable is used.
+    pub fn finish(self) -> Result<Q, BindError> {
+        if let Some(e) = self.error {
+            return Err(e);
+        }
+        if let Some(unbound) = self
+            .placeholders
+            .iter()
+            .find(|p| !self.bindings.contains_key(*p))
+        {
+            return Err(BindError::Unbound(unbound.clone()));
+        }
+
+        let mut substitution = Substitution {
+            bindings: &self.bindings,
+            used: HashSet::new(),
+        };
+        let query = substitution.query(self.query)?;
+        if let Some(unused) = self
+            .bindings
+            .keys()
+            .find(|name| !substitution.used.contains(&***name))
+        {
+            return Err(BindError::UnknownVariable(unused.clone()));
+        }
+
+        // Re-parsing catches algebra that has no valid SPARQL rendering.
+        Ok(query.to_string().parse()?)
+    }
+}
+
+impl<Q: QueryString> FromStr for PreparedQuery<Q> {
+    type Err = QueryStringError;
+
+    fn from_str(s: &str) -> Result<Self, QueryStringError> {
+        let normalized = Q::from_str(s)?;
+        let placeholders = sparql_http_client_scan::placeholders(s);
+        let placeholders = placeholders.iter().map(|p| &**p).collect::<Vec<_>>();
+        Ok(Self::new_unchecked(&normalized, &placeholders))
+    }
+}
+
+impl<Q: QueryString> From<Q> for PreparedQuery<Q> {
+    fn from(query: Q) -> Self {
+        Self::new_unchecked(&query, &[])
+    }
+}
+
+impl<Q> fmt::Debug for PreparedQuery<Q> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PreparedQuery")
+            .field("query", &self.query.to_string())
+            .field("placeholders", &self.placeholders)
+            .field("bindings", &self.bindings)
+            .finish()
+    }
+}
+
+fn ground_term(var: &str, term: RDFTerm) -> Result<GroundTerm, BindError> {
+    let invalid = |reason: &dyn fmt::Display| BindError::InvalidTerm {
+        var: var.into(),
+        reason: reason.to_string().into(),
+    };
+    let iri = |iri: Box<str>| NamedNode::new(iri).map_err(|e| invalid(&e));
+    Ok(match term.kind {
+        RDFType::IRI => GroundTerm::NamedNode(iri(term.value)?),
+        RDFType::BlankNode => return Err(invalid(&"blank nodes cannot be bound")),
+        RDFType::Triple(_) => return Err(invalid(&"triple terms cannot be bound")),
+        RDFType::Literal(LiteralType::Plain) => {
+            GroundTerm::Literal(Literal::new_simple_literal(term.value))
+        }
+        RDFType::Literal(LiteralType::Lang(lang)) => GroundTerm::Literal(
+            Literal::new_language_tagged_literal(term.value, lang).map_err(|e| invalid(&e))?,
+        ),
+        #[cfg(feature = "sparql-12")]
+        RDFType::Literal(LiteralType::DirLang(lang, direction)) => GroundTerm::Literal(
+            Literal::new_directional_language_tagged_literal(term.value, lang, direction)
+                .map_err(|e| invalid(&e))?,
+        ),
+        #[cfg(not(feature = "sparql-12"))]
+        RDFType::Literal(LiteralType::DirLang(..)) => {
+            return Err(invalid(
+                &"directional language tags need the `sparql-12` feature",
+            ))
+        }
+        RDFType::Literal(LiteralType::Datatype(dt)) => {
+            GroundTerm::Literal(Literal::new_typed_literal(term.value, iri(dt)?))
+        }
+    })
+}
+
+/// Replaces bound variables throughout a query, recording which ones occur.
+struct Substitution<'a> {
+    bindings: &'a HashMap<Box<str>, GroundTerm>,
+    used: HashSet<&'a str>,
+}
+
+impl<'a> Substitution<'a> {
+    fn lookup(&mut self, var: &Variable) -> Option<&'a GroundTerm> {
+        let (name, term) = self.bindings.get_key_value(var.as_str())?;
+        self.used.insert(name);
+        Some(term)
+    }
+
+    fn query(&mut self, query: Query) -> Result<Query, BindError> {
+        Ok(match query {
+            Query::Select {
+                dataset,
+                pattern,
+                base_iri,
+            } => Query::Select {
+                dataset,
+                pattern: self.pattern(pattern)?,
+                base_iri,
+            },
+            Query::Construct {
+                template,
+                dataset,
+                pattern,
+                base_iri,
+            } => Query::Construct {
+                template: template
+                    .into_iter()
+                    .map(|t| self.triple(t))
+                    .collect::<Result<_, _>>()?,
+                dataset,
+                pattern: self.pattern(pattern)?,
+                base_iri,
+            },
+            Query::Describe {
+                dataset,
+                pattern,
+                base_iri,
+            } => Query::Describe {
+                dataset,
+                pattern: self.pattern(pattern)?,
+                base_iri,
+            },
+            Query::Ask {
+                dataset,
+                pattern,
+                base_iri,
+            } => Query::Ask {
+                dataset,
+                pattern: self.pattern(pattern)?,
+                base_iri,
+            },
+        })
+    }
+
+    fn boxed(&mut self, pattern: GraphPattern) -> Result<Box<GraphPattern>, BindError> {
+        Ok(Box::new(self.pattern(pattern)?))
+    }
+
+    fn pattern(&mut self, pattern: GraphPattern) -> Result<GraphPattern, BindError> {
+        Ok(match pattern {
+            GraphPattern::Bgp { patterns } => GraphPattern::Bgp {
+                patterns: patterns
+                    .into_iter()
+                    .map(|t| self.triple(t))
+                    .collect::<Result<_, _>>()?,
+            },
+            GraphPattern::Path {
+                subject,
+                path,
+                object,
+            } => GraphPattern::Path {
+                subject: self.term(subject)?,
+                path,
+                object: self.term(object)?,
+            },
+            GraphPattern::Join { left, right } => GraphPattern::Join {
+                left: self.boxed(*left)?,
+                right: self.boxed(*right)?,
+            },
+            GraphPattern::LeftJoin {
+                left,
+                right,
+                expression,
+            } => GraphPattern::LeftJoin {
+                left: self.boxed(*left)?,
+                right: self.boxed(*right)?,
+                expression: expression.map(|e| self.expression(e)).transpose()?,
+            },
+            GraphPattern::Filter { expr, inner } => GraphPattern::Filter {
+                expr: self.expression(expr)?,
+                inner: self.boxed(*inner)?,
+            },
+            GraphPattern::Union { left, right } => GraphPattern::Union {
+                left: self.boxed(*left)?,
+                right: self.boxed(*right)?,
+            },
+            GraphPattern::Graph { name, inner } => GraphPattern::Graph {
+                name: self.named_node(name)?,
+                inner: self.boxed(*inner)?,
+            },
+            GraphPattern::Extend {
+                inner,
+                variable,
+                expression,
+            } => {
+                if self.lookup(&variable).is_some() {
+                    return Err(BindError::Assigned(variable.as_str().into()));
+                }
+                GraphPattern::Extend {
+                    inner: self.boxed(*inner)?,
+                    variable,
+                    expression: self.expression(expression)?,
+                }
+            }
+            GraphPattern::Minus { left, right } => GraphPattern::Minus {
+                left: self.boxed(*left)?,
+                right: self.boxed(*right)?,
+            },
+            GraphPattern::Values {
+                variables,
+                bindings,
+            } => self.values(variables, bindings),
+            GraphPattern::OrderBy { inner, expression } => GraphPattern::OrderBy {
+                inner: self.boxed(*inner)?,
+                expression: expression
+                    .into_iter()
+                    .map(|e| {
+                        Ok(match e {
+                            OrderExpression::Asc(e) => OrderExpression::Asc(self.expression(e)?),
+                            OrderExpression::Desc(e) => OrderExpression::Desc(self.expression(e)?),
+                        })
+                    })
+                    .collect::<Result<_, BindError>>()?,
+            },
+            GraphPattern::Project { inner, variables } => {
+                let mut inner = self.boxed(*inner)?;
+                // A projected placeholder stays in the results as a constant column.
+                for variable in &variables {
+                    if let Some(term) = self.lookup(variable) {
+                        inner = Box::new(GraphPattern::Extend {
+                            inner,
+                            variable: variable.clone(),
+                            expression: ground_expression(variable, term)?,
+                        });
+                    }
+                }
+                GraphPattern::Project { inner, variables }
+            }
+            GraphPattern::Distinct { inner } => GraphPattern::Distinct {
+                inner: self.boxed(*inner)?,
+            },
+            GraphPattern::Reduced { inner } => GraphPattern::Reduced {
+                inner: self.boxed(*inner)?,
+            },
+            GraphPattern::Slice {
+                inner,
+                start,
+                length,
+            } => GraphPattern::Slice {
+                inner: self.boxed(*inner)?,
+                start,
+                length,
+            },
+            GraphPattern::Group {
+                inner,
+                variables,
+                aggregates,
+            } => GraphPattern::Group {
+                inner: self.boxed(*inner)?,
+                // Grouping by a constant does not split the solutions.
+                variables: variables
+                    .into_iter()
+                    .filter(|v| self.lookup(v).is_none())
+                    .collect(),
+                aggregates: aggregates
+                    .into_iter()
+                    .map(|(variable, aggregate)| {
+                        if self.lookup(&variable).is_some() {
+                            return Err(BindError::Assigned(variable.as_str().into()));
+                        }
+                        Ok((variable, self.aggregate(aggregate)?))
+                    })
+                    .collect::<Result<_, _>>()?,
+            },
+            GraphPattern::Service {
+                name,
+                inner,
+                silent,
+            } => GraphPattern::Service {
+                name: self.named_node(name)?,
+                inner: self.boxed(*inner)?,
+                silent,
+            },
+            #[allow(unreachable_patterns)]
+            other => return self.unsupported(other.to_string()),
+        })
+    }
+
+    /// Keeps the rows of a `VALUES` table that agree with the bound values, and
+    /// drops the bound columns.
+    fn values(
+        &mut self,
+        variables: Vec<Variable>,
+        bindings: Vec<Vec<Option<GroundTerm>>>,
+    ) -> GraphPattern {
+        let bound = variables.iter().map(|v| self.lookup(v)).collect::<Vec<_>>();
+        let bindings = bindings
+            .into_iter()
+            .filter(|row| {
+                row.iter()
+                    .zip(&bound)
+                    .all(|(cell, bound)| match (cell, bound) {
+                        (Some(cell), Some(bound)) => cell == *bound,
+                        _ => true,
+                    })
+            })
+            .map(|row| {
+                row.into_iter()
+                    .zip(&bound)
+                    .filter(|(_, bound)| bound.is_none())
+                    .map(|(cell, _)| cell)
+                    .collect()
+            })
+            .collect();
+        let variables = variables
+            .into_iter()
+            .zip(&bound)
+            .filter(|(_, bound)| bound.is_none())
+            .map(|(v, _)| v)
+            .collect();
+        GraphPattern::Values {
+            variables,
+            bindings,
+        }
+    }
+
+    fn triple(&mut self, triple: TriplePattern) -> Result<TriplePattern, BindError> {
+        Ok(TriplePattern {
+            subject: self.term(triple.subject)?,
+            predicate: self.named_node(triple.predicate)?,
+            object: self.term(triple.object)?,
+        })
+    }
+
+    fn term(&mut self, term: TermPattern) -> Result<TermPattern, BindError> {
+        Ok(match term {
+            TermPattern::Variable(v) => match self.lookup(&v) {
+                Some(GroundTerm::NamedNode(node)) => TermPattern::NamedNode(node.clone()),
+                Some(GroundTerm::Literal(literal)) => TermPattern::Literal(literal.clone()),
+                #[allow(unreachable_patterns)]
+                Some(_) => return self.unsupported(v.to_string()),
+                None => TermPattern::Variable(v),
+            },
+            TermPattern::NamedNode(_) | TermPattern::BlankNode(_) | TermPattern::Literal(_) => term,
+            #[allow(unreachable_patterns)]
+            other => return self.unsupported(other.to_string()),
+        })
+    }
+
+    fn named_node(&mut self, pattern: NamedNodePattern) -> Result<NamedNodePattern, BindError> {
+        Ok(match pattern {
+            NamedNodePattern::Variable(v) => match self.lookup(&v) {
+                Some(GroundTerm::NamedNode(node)) => NamedNodePattern::NamedNode(node.clone()),
+                Some(_) => return Err(BindError::NotAnIri(v.as_str().into())),
+                None => NamedNodePattern::Variable(v),
+            },
+            NamedNodePattern::NamedNode(_) => pattern,
+        })
+    }
+
+    fn aggregate(
+        &mut self,
+        aggregate: AggregateExpression,
+    ) -> Result<AggregateExpression, BindError> {
+        Ok(match aggregate {
+            AggregateExpression::CountSolutions { .. } => aggregate,
+            AggregateExpression::FunctionCall {
+                name,
+                expr,
+                distinct,
+            } => AggregateExpression::FunctionCall {
+                name,
+                expr: self.expression(expr)?,
+                distinct,
+            },
+        })
+    }
+
+    fn boxed_expression(&mut self, e: Expression) -> Result<Box<Expression>, BindError> {
+        Ok(Box::new(self.expression(e)?))
+    }
+
+    fn expressions(&mut self, es: Vec<Expression>) -> Result<Vec<Expression>, BindError> {
+        es.into_iter().map(|e| self.expression(e)).collect()
+    }
+
+    fn expression(&mut self, expression: Expression) -> Result<Expression, BindError> {
+        use Expression as E;
+
+        Ok(match expression {
+            E::NamedNode(_) | E::Literal(_) => expression,
+            E::Variable(v) => match self.lookup(&v) {
+                Some(term) => ground_expression(&v, term)?,
+                None => E::Variable(v),
+            },
+            E::Bound(v) => match self.lookup(&v) {
+                Some(_) => E::Literal(Literal::from(true)),
+                None => E::Bound(v),
+            },
+            E::Or(a, b) => E::Or(self.boxed_expression(*a)?, self.boxed_expression(*b)?),
+            E::And(a, b) => E::And(self.boxed_expression(*a)?, self.boxed_expression(*b)?),
+            E::Equal(a, b) => E::Equal(self.boxed_expression(*a)?, self.boxed_expression(*b)?),
+            E::SameTerm(a, b) => {
+                E::SameTerm(self.boxed_expression(*a)?, self.boxed_expression(*b)?)
+            }
+            E::Greater(a, b) => E::Greater(self.boxed_expression(*a)?, self.boxed_expression(*b)?),
+            E::GreaterOrEqual(a, b) => {
+                E::GreaterOrEqual(self.boxed_expression(*a)?, self.boxed_expression(*b)?)
+            }
+            E::Less(a, b) => E::Less(self.boxed_expression(*a)?, self.boxed_expression(*b)?),
+            E::LessOrEqual(a, b) => {
+                E::LessOrEqual(self.boxed_expression(*a)?, self.boxed_expression(*b)?)
+            }
+            E::In(a, list) => E::In(self.boxed_expression(*a)?, self.expressions(list)?),
+            E::Add(a, b) => E::Add(self.boxed_expression(*a)?, self.boxed_expression(*b)?),
+            E::Subtract(a, b) => {
+                E::Subtract(self.boxed_expression(*a)?, self.boxed_expression(*b)?)
+            }
+            E::Multiply(a, b) => {
+                E::Multiply(self.boxed_expression(*a)?, self.boxed_expression(*b)?)
+            }
+            E::Divide(a, b) => E::Divide(self.boxed_expression(*a)?, self.boxed_expression(*b)?),
+            E::UnaryPlus(a) => E::UnaryPlus(self.boxed_expression(*a)?),
+            E::UnaryMinus(a) => E::UnaryMinus(self.boxed_expression(*a)?),
+            E::Not(a) => E::Not(self.boxed_expression(*a)?),
+            E::Exists(pattern) => E::Exists(self.boxed(*pattern)?),
+            E::If(a, b, c) => E::If(
+                self.boxed_expression(*a)?,
+                self.boxed_expression(*b)?,
+                self.boxed_expression(*c)?,
+            ),
+            E::Coalesce(list) => E::Coalesce(self.expressions(list)?),
+            E::FunctionCall(function, args) => E::FunctionCall(function, self.expressions(args)?),
+        })
+    }
+
+    /// Fails if any bound variable occurs in `fragment`, which could not be rewritten.
+    fn unsupported<T>(&self, fragment: String) -> Result<T, BindError> {
+        let var = self
+            .bindings
+            .keys()
+            .find(|name| fragment.contains(&format!("?{name}")))
+            .map_or_else(|| fragment.as_str().into(), Clone::clone);
+        Err(BindError::Unsupported(var))
+    }
+}
+
+fn ground_expression(var: &Variable, term: &GroundTerm) -> Result<Expression, BindError> {
+    match term {
+        GroundTerm::NamedNode(node) => Ok(Expression::NamedNode(node.clone())),
+        GroundTerm::Literal(literal) => Ok(Expression::Literal(literal.clone())),
+        #[allow(unreachable_patterns)]
+        _ => Err(BindError::Unsupported(var.as_str().into())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AskQueryString, SelectQueryString};
+
+    fn prepare(s: &str) -> PreparedQuery<SelectQueryString> {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn substitute_everywhere() {
+        let query = prepare(
+            r#"SELECT ?s ?p WHERE {
+                ?s ?p $o
+                FILTER(BOUND($o) && ?s != $o)
+                VALUES (?p $o) { (<http://ex.org/a> 1) (<http://ex.org/b> 2) }
+            }"#,
+        )
+        .bind("o", 2)
+        .finish()
+        .unwrap();
+
+        assert!(!query.contains("?o"), "{query}");
+        assert!(query.contains(r#""2"^^<http://www.w3.org/2001/XMLSchema#integer>"#));
+        assert!(!query.contains("<http://ex.org/a>"), "{query}");
+    }
+
+    #[test]
+    fn substitute_inside_subquery_scope() {
+        let query = prepare("SELECT ?s WHERE { ?s ?p $o { SELECT ?s WHERE { ?s ?q ?o } } }")
+            .bind("o", 1)
+            .finish()
+            .unwrap();
+
+        assert!(!query.contains("?o"), "{query}");
+    }
+
+    #[test]
+    fn injection_stays_literal() {
+        let query = prepare("SELECT ?s WHERE { ?s ?p $name }")
+            .bind("name", r#"x" } ; DROP ALL ; SELECT * { ""#)
+            .finish()
+            .unwrap();
+
+        let parsed = SparqlParser::new().parse_query(&query).unwrap();
+        let Query::Select { pattern, .. } = parsed else {
+            panic!("not a select");
+        };
+        assert!(pattern.to_string().contains(r#"x\" } ; DROP ALL"#));
+    }
+
+    #[test]
+    fn projected_placeholder_is_constant() {
+        let query = prepare("SELECT ?s $lang WHERE { ?s ?p ?o FILTER(LANG(?o) = $lang) }")
+            .bind("lang", "en")
+            .finish()
+            .unwrap();
+
+        assert!(query.contains(r#"("en" AS ?lang)"#), "{query}");
+    }
+
+    #[test]
+    fn errors() {
+        let err = prepare("SELECT ?s WHERE { ?s ?p $o }")
+            .finish()
+            .unwrap_err();
+        assert!(matches!(err, BindError::Unbound(v) if &*v == "o"));
+
+        let err = prepare("SELECT ?s WHERE { ?s ?p $o }")
+            .bind("o", 1)
+            .bind("typo", 1)
+            .finish()
+            .unwrap_err();
+        assert!(matches!(err, BindError::UnknownVariable(v) if &*v == "typo"));
+
+        let err = prepare("SELECT ?s WHERE { ?s $p ?o }")
+            .bind("p", "not an IRI")
+            .finish()
+            .unwrap_err();
+        assert!(matches!(err, BindError::NotAnIri(v) if &*v == "p"));
+
+        let err = prepare("SELECT ?s WHERE { ?s ?p ?o BIND(1 AS $x) }")
+            .bind("x", 1)
+            .finish()
+            .unwrap_err();
+        assert!(matches!(err, BindError::Assigned(v) if &*v == "x"));
+
+        let err = prepare("SELECT ?s WHERE { ?s ?p $o }")
+            .bind("o", RDFTerm::iri("not absolute"))
+            .finish()
+            .unwrap_err();
+        assert!(matches!(err, BindError::InvalidTerm { .. }));
+    }
+
+    #[test]
+    fn optional_question_mark_binding() {
+        let ask: AskQueryString = PreparedQuery::from(
+            "ASK { ?s <http://ex.org/p> ?o }"
+                .parse::<AskQueryString>()
+                .unwrap(),
+        )
+        .bind("s", RDFTerm::iri("http://ex.org/s"))
+        .finish()
+        .unwrap();
+
+        assert!(
+            ask.contains("<http://ex.org/s> <http://ex.org/p> ?o"),
+            "{ask}"
+        );
+    }
+}
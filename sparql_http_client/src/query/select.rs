@@ -91,6 +91,15 @@ mod tests {
         } LIMIT 3
     "#;
 
+    #[cfg(feature = "sparql-12")]
+    #[test]
+    fn triple_term_syntax() -> anyhow::Result<()> {
+        "SELECT ?a WHERE { ?s ?p <<( ?a ?b ?c )>> }".parse::<SelectQueryString>()?;
+        "SELECT ?t WHERE { ?s ?p ?t FILTER(isTRIPLE(?t)) }".parse::<SelectQueryString>()?;
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn run_stream() -> anyhow::Result<()> {
         use futures_util::StreamExt;
@@ -111,6 +120,24 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn run_json() -> anyhow::Result<()> {
+        use crate::response::ResultFormat;
+
+        let qs: SelectQueryString = QUERY.parse()?;
+        let rows = Endpoint::new(SparqlClient::default(), WIKIDATA)
+            .build_query(qs)
+            .with_result_format(ResultFormat::Json)
+            .run()
+            .await?
+            .collect()
+            .await?;
+
+        assert_eq!(rows.len(), 3);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn run_collect() -> anyhow::Result<()> {
         let qs: SelectQueryString = QUERY.parse()?;
@@ -0,0 +1,105 @@
+use std::fmt;
+use std::ops::Deref;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use spargebra::SparqlParser;
+
+use super::QueryStringError;
+
+/// An owned, validated, normalized SPARQL 1.1 Update string.
+///
+/// Covers every update operation: `INSERT DATA`, `DELETE DATA`,
+/// `DELETE`/`INSERT … WHERE`, `LOAD`, `CLEAR`, `CREATE`, `DROP`, `COPY`,
+/// `MOVE` and `ADD`, possibly several separated by `;`.
+///
+/// Parse from a `&str` at runtime via [`str::parse`]:
+///
+/// ```
+/// use sparql_http_client::UpdateString;
+///
+/// let us: UpdateString = r#"
+///     INSERT DATA { <http://example.org/alice> <http://xmlns.com/foaf/0.1/name> "Alice" }
+/// "#.parse().unwrap();
+/// ```
+///
+/// A query is not an update, so parsing one fails with a
+/// [`QueryStringError::Syntax`]:
+///
+/// ```
+/// use sparql_http_client::UpdateString;
+///
+/// let result = "SELECT ?s WHERE { ?s ?p ?o }".parse::<UpdateString>();
+/// assert!(result.is_err());
+/// ```
+///
+/// Execute it with [`Endpoint::update`](crate::Endpoint::update), or use the
+/// [`update!`](crate::update!) macro for compile-time validation.
+///
+/// [`Deref`] and [`Display`](std::fmt::Display) both yield the normalised update string.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UpdateString(Arc<str>);
+
+impl UpdateString {
+    #[doc(hidden)]
+    pub fn new_unchecked(s: &str) -> Self {
+        Self(Arc::from(s))
+    }
+}
+
+impl FromStr for UpdateString {
+    type Err = QueryStringError;
+
+    fn from_str(s: &str) -> Result<Self, QueryStringError> {
+        let u = SparqlParser::new().parse_update(s)?;
+        Ok(Self(u.to_string().into()))
+    }
+}
+
+impl Deref for UpdateString {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for UpdateString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_operations() -> anyhow::Result<()> {
+        let us: UpdateString = r#"
+            PREFIX ex: <http://example.org/>
+
+            INSERT DATA { ex:a ex:p "1" } ;
+            DELETE { ?s ex:p ?o } INSERT { ?s ex:q ?o } WHERE { ?s ex:p ?o } ;
+            LOAD SILENT <http://example.org/data.ttl> INTO GRAPH ex:g ;
+            CLEAR GRAPH ex:g ;
+            CREATE GRAPH ex:h ;
+            COPY ex:g TO ex:h ;
+            MOVE DEFAULT TO ex:g ;
+            ADD ex:g TO DEFAULT ;
+            DROP ALL
+        "#
+        .parse()?;
+
+        assert!(us.starts_with("INSERT DATA"));
+        assert!(us.trim_end().ends_with("DROP ALL ;"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn reject_query() {
+        let err = "ASK { ?s ?p ?o }".parse::<UpdateString>().unwrap_err();
+        assert!(matches!(err, QueryStringError::Syntax(_)));
+    }
+}
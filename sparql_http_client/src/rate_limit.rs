@@ -0,0 +1,189 @@
+//! Client-side limits on the requests sent to an endpoint.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use futures_util::StreamExt;
+use reqwest::{Response, ResponseBuilderExt};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Limits on the queries and updates in flight to an endpoint, to stay within
+/// its usage policy.
+///
+/// Set with [`Endpoint::with_rate_limit`]. The limits are shared by every clone
+/// of the endpoint, so tasks that each got a clone are limited together.
+///
+/// A query counts as in flight from the moment it is sent until its response
+/// has been read to the end or dropped, so a SELECT whose rows are still
+/// streaming holds its slot. Every request, including each retry, counts
+/// towards the requests per window.
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use sparql_http_client::{Endpoint, RateLimit, SparqlClient};
+///
+/// // Wikidata allows 5 concurrent queries per client.
+/// let endpoint = Endpoint::new(SparqlClient::default(), "https://query.wikidata.org/sparql")
+///     .with_rate_limit(
+///         RateLimit::default()
+///             .with_max_concurrent(5)
+///             .with_max_requests(60, Duration::from_secs(60)),
+///     );
+///
+/// let shared = endpoint.clone();
+/// ```
+///
+/// [`Endpoint::with_rate_limit`]: crate::Endpoint::with_rate_limit
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RateLimit {
+    max_concurrent: Option<usize>,
+    max_requests: Option<(usize, Duration)>,
+}
+
+impl RateLimit {
+    /// Allows at most `max` requests in flight at once; further queries wait
+    /// for one to finish.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max` is zero.
+    pub fn with_max_concurrent(mut self, max: usize) -> Self {
+        assert!(max > 0, "at least one request must be allowed in flight");
+        self.max_concurrent = Some(max);
+        self
+    }
+
+    /// Allows at most `max` requests to be sent in any `window` of time;
+    /// further requests wait until the oldest one leaves the window.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max` is zero.
+    pub fn with_max_requests(mut self, max: usize, window: Duration) -> Self {
+        assert!(max > 0, "at least one request must be allowed per window");
+        self.max_requests = Some((max, window));
+        self
+    }
+}
+
+/// The state of a [`RateLimit`], shared by the clones of an endpoint.
+pub(crate) struct Limiter {
+    limit: RateLimit,
+    permits: Option<Arc<Semaphore>>,
+    sent: Mutex<VecDeque<Instant>>,
+}
+
+impl Limiter {
+    pub(crate) fn new(limit: RateLimit) -> Self {
+        Self {
+            permits: limit
+                .max_concurrent
+                .map(|max| Arc::new(Semaphore::new(max))),
+            sent: Mutex::new(VecDeque::new()),
+            limit,
+        }
+    }
+
+    /// Waits for a request to be allowed in flight. The returned permit must
+    /// be kept until the response has been read, see [`hold`].
+    pub(crate) async fn acquire(&self) -> Option<OwnedSemaphorePermit> {
+        let permits = Arc::clone(self.permits.as_ref()?);
+        Some(
+            permits
+                .acquire_owned()
+                .await
+                .expect("semaphore is never closed"),
+        )
+    }
+
+    /// Waits until a request can be sent within the request window, and
+    /// records it.
+    pub(crate) async fn wait_for_window(&self) {
+        let Some((max, window)) = self.limit.max_requests else {
+            return;
+        };
+        loop {
+            let wait = {
+                let mut sent = self.sent.lock().unwrap_or_else(PoisonError::into_inner);
+                let now = Instant::now();
+                while sent.front().is_some_and(|t| now - *t >= window) {
+                    sent.pop_front();
+                }
+                if sent.len() < max {
+                    sent.push_back(now);
+                    return;
+                }
+                window - (now - sent[0])
+            };
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+impl fmt::Debug for Limiter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.limit.fmt(f)
+    }
+}
+
+/// Moves `permit` into the body of `response`, so that it is released once
+/// the body has been read to the end or dropped.
+pub(crate) fn hold(response: Response, permit: OwnedSemaphorePermit) -> Response {
+    let mut builder = http::Response::builder()
+        .status(response.status())
+        .version(response.version())
+        .url(response.url().clone());
+    if let Some(headers) = builder.headers_mut() {
+        *headers = response.headers().clone();
+    }
+    let body = response.bytes_stream().map(move |chunk| {
+        let _in_flight = &permit;
+        chunk
+    });
+    builder
+        .body(reqwest::Body::wrap_stream(body))
+        .expect("parts of a valid response")
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::FutureExt;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn concurrency() {
+        let limiter = Limiter::new(RateLimit::default().with_max_concurrent(1));
+        let permit = limiter.acquire().await.unwrap();
+        assert!(limiter.acquire().now_or_never().is_none());
+
+        let response = http::Response::builder()
+            .header("content-type", "text/plain")
+            .body("body")
+            .unwrap()
+            .into();
+        let response = hold(response, permit);
+        assert!(limiter.acquire().now_or_never().is_none());
+        assert_eq!(response.headers()["content-type"], "text/plain");
+        assert_eq!(response.text().await.unwrap(), "body");
+        assert!(limiter.acquire().now_or_never().is_some());
+    }
+
+    #[tokio::test]
+    async fn window() {
+        let limiter =
+            Limiter::new(RateLimit::default().with_max_requests(2, Duration::from_millis(100)));
+        assert!(limiter.acquire().await.is_none());
+
+        let start = Instant::now();
+        limiter.wait_for_window().await;
+        limiter.wait_for_window().await;
+        assert!(start.elapsed() < Duration::from_millis(100));
+        limiter.wait_for_window().await;
+        assert!(start.elapsed() >= Duration::from_millis(100));
+    }
+}
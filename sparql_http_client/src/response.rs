@@ -1,7 +1,32 @@
 mod ask;
+mod construct;
+mod de;
+mod describe;
+mod format;
+mod interop;
+mod json;
+mod literal;
+pub(crate) mod row;
 mod select;
+mod status;
 mod term;
+mod triple;
+mod turtle;
+mod update;
+#[cfg(feature = "xml")]
+mod xml;
 
 pub use ask::*;
+pub use construct::*;
+pub use de::*;
+pub use describe::*;
+pub use format::*;
+pub use interop::*;
+pub use literal::*;
+pub use row::{from_term, FromRow};
 pub use select::*;
+pub use status::*;
 pub use term::*;
+pub use triple::*;
+pub use turtle::*;
+pub use update::*;
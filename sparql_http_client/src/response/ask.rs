@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
 
+use super::format::ResultFormat;
+use super::select::StreamError;
+
 /// The deserialized response to a SPARQL ASK query.
 ///
 /// Convert to `bool` via [`From`] to extract the result:
@@ -26,6 +29,32 @@ pub struct AskHead {
     pub link: Option<Box<[Box<str>]>>,
 }
 
+impl AskQueryResponse {
+    /// Parses `response` according to its `Content-Type`, or as `requested`
+    /// when the endpoint did not send one.
+    pub(crate) async fn from_response(
+        response: reqwest::Response,
+        requested: ResultFormat,
+    ) -> Result<Self, StreamError> {
+        let format = requested.negotiated_ask(&response)?;
+        match format {
+            ResultFormat::Json => Ok(response.json().await?),
+            #[cfg(feature = "xml")]
+            ResultFormat::Xml => {
+                use futures_util::StreamExt;
+
+                let byte_stream = response
+                    .bytes_stream()
+                    .map(|r| r.map_err(std::io::Error::other));
+                super::xml::ask(tokio_util::io::StreamReader::new(byte_stream)).await
+            }
+            other => Err(StreamError::UnsupportedContentType(
+                other.media_type().into(),
+            )),
+        }
+    }
+}
+
 /// Extracts the boolean result, consuming the response.
 impl From<AskQueryResponse> for bool {
     fn from(r: AskQueryResponse) -> Self {
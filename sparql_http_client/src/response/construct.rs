@@ -0,0 +1,165 @@
+use std::io;
+use std::pin::Pin;
+
+use futures_util::{stream, stream::Stream, StreamExt, TryStreamExt};
+use oxttl::TurtleParser;
+use reqwest::header::CONTENT_TYPE;
+use tokio_util::io::StreamReader;
+
+use super::select::{ParseError, StreamError};
+use super::triple::Triple;
+
+/// A streaming SPARQL CONSTRUCT response received as N-Triples or Turtle.
+///
+/// Returned by [`SparqlQuery<ConstructQueryString>::run`](crate::SparqlQuery::run).
+/// Triples are yielded one at a time via
+/// [`into_triples`](ConstructQueryResponse::into_triples) as they arrive over the network.
+///
+/// # Example
+///
+/// ```no_run
+/// use futures_util::StreamExt;
+/// use sparql_http_client::{Endpoint, SparqlClient, ConstructQueryString};
+///
+/// # #[tokio::main] async fn main() -> anyhow::Result<()> {
+/// let qs: ConstructQueryString = "CONSTRUCT { ?s ?p ?o } WHERE { ?s ?p ?o } LIMIT 10".parse()?;
+/// let stream = Endpoint::new(SparqlClient::default(), "https://example.org/sparql")
+///     .build_query(qs)
+///     .run()
+///     .await?;
+///
+/// let mut triples = std::pin::pin!(stream.into_triples());
+/// while let Some(triple) = triples.next().await {
+///     println!("{:?}", triple?);
+/// }
+/// # Ok(()) }
+/// ```
+pub struct ConstructQueryResponse {
+    triples: Pin<Box<dyn Stream<Item = Result<Triple, StreamError>> + Send>>,
+}
+
+impl ConstructQueryResponse {
+    /// Media types the triple parser accepts, including legacy N-Triples and Turtle labels.
+    const MEDIA_TYPES: &'static [&'static str] = &[
+        "application/n-triples",
+        "text/turtle",
+        "text/plain",
+        "application/x-turtle",
+    ];
+
+    pub(crate) fn from_response(response: reqwest::Response) -> Result<Self, StreamError> {
+        if let Some(content_type) = response.headers().get(CONTENT_TYPE) {
+            let content_type = String::from_utf8_lossy(content_type.as_bytes());
+            let essence = content_type.split(';').next().unwrap_or_default().trim();
+            if !Self::MEDIA_TYPES
+                .iter()
+                .any(|t| essence.eq_ignore_ascii_case(t))
+            {
+                return Err(StreamError::UnsupportedContentType(content_type.into()));
+            }
+        }
+
+        // N-Triples is a subset of Turtle, so a single parser covers both.
+        let parser = TurtleParser::new();
+        let parser = match parser.clone().with_base_iri(response.url().as_str()) {
+            Ok(with_base) => with_base,
+            Err(_) => parser,
+        };
+
+        let byte_stream = response.bytes_stream().map(|r| r.map_err(io::Error::other));
+        let reader = parser.for_tokio_async_reader(StreamReader::new(byte_stream));
+
+        let triples = Box::pin(stream::unfold(reader, |mut reader| async move {
+            let triple = reader.next().await?;
+            let triple = triple
+                .map(Triple::from)
+                .map_err(|e| StreamError::from(ParseError::from(e)));
+            Some((triple, reader))
+        }));
+
+        Ok(Self { triples })
+    }
+
+    /// Consumes this value and returns the triple stream.
+    pub fn into_triples(self) -> impl Stream<Item = Result<Triple, StreamError>> {
+        self.triples
+    }
+
+    /// Collects all triples into a [`Vec`], consuming this response.
+    ///
+    /// Returns an error if any triple fails to parse or if the HTTP transfer fails.
+    pub async fn collect(self) -> Result<Vec<Triple>, StreamError> {
+        self.triples.try_collect().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use oxttl::TurtleParser;
+
+    use super::*;
+    use crate::response::{LiteralType, RDFType};
+
+    #[test]
+    fn convert_triples() -> anyhow::Result<()> {
+        let turtle = r#"
+            @prefix ex: <http://example.org/> .
+            ex:s ex:p "hello"@en, 42 ;
+                 ex:q [ ex:r "x" ] .
+        "#;
+
+        let triples = TurtleParser::new()
+            .for_slice(turtle)
+            .map(|t| Ok(Triple::from(t?)))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        assert_eq!(triples.len(), 4);
+        assert_eq!(&*triples[0].subject.value, "http://example.org/s");
+        assert_eq!(triples[0].object.lang(), Some("en"));
+        assert_eq!(
+            triples[1].object.datatype(),
+            Some("http://www.w3.org/2001/XMLSchema#integer")
+        );
+        assert!(triples[2].object.is_blank_node());
+        assert_eq!(triples[3].object.kind, RDFType::Literal(LiteralType::Plain));
+
+        Ok(())
+    }
+
+    #[cfg(feature = "sparql-12")]
+    #[test]
+    fn convert_rdf_12_terms() -> anyhow::Result<()> {
+        use crate::response::{BaseDirection, RDFTerm};
+
+        let turtle = r#"
+            @prefix ex: <http://example.org/> .
+            ex:s ex:p "مرحبا"@ar--rtl, <<( ex:a ex:b "c"@EN )>> .
+        "#;
+
+        let triples = TurtleParser::new()
+            .for_slice(turtle)
+            .map(|t| Ok(Triple::from(t?)))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        assert_eq!(triples[0].object.direction(), Some(BaseDirection::Rtl));
+        let quoted = triples[1].object.as_triple().unwrap();
+        assert_eq!(quoted.subject, RDFTerm::iri("http://example.org/a"));
+        assert_eq!(quoted.object, RDFTerm::lang_literal("c", "en"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn reject_rdf_xml() {
+        let response: reqwest::Response = http::Response::builder()
+            .header(CONTENT_TYPE, "application/rdf+xml")
+            .body("<rdf:RDF/>")
+            .unwrap()
+            .into();
+
+        assert!(matches!(
+            ConstructQueryResponse::from_response(response),
+            Err(StreamError::UnsupportedContentType(_))
+        ));
+    }
+}
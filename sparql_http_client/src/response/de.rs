@@ -0,0 +1,669 @@
+use serde::de::{
+    self, Deserialize, DeserializeSeed, IntoDeserializer, MapAccess, SeqAccess, Visitor,
+};
+use thiserror::Error;
+
+use super::literal::{
+    collapse, is_integer_type, parse_boolean, parse_decimal, parse_double, parse_integer_of,
+    LiteralError,
+};
+#[cfg(any(feature = "chrono", feature = "time"))]
+use super::literal::{is_date_time_type, parse_date, parse_date_time};
+use super::select::Row;
+use super::term::{
+    LiteralType, RDFTerm, RDFType, TERM_BLANK_NODE, TERM_DATATYPE, TERM_DIR_LANG, TERM_IRI,
+    TERM_LANG, TERM_PLAIN, TERM_TOKEN, TERM_TRIPLE,
+};
+
+const XSD: &str = "http://www.w3.org/2001/XMLSchema#";
+
+/// Error returned when a [`Row`] cannot be deserialized into the requested type.
+///
+/// Yielded as [`ParseError::Row`](super::ParseError::Row) by
+/// [`SelectQueryResponse::into_typed`](super::SelectQueryResponse::into_typed).
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+#[error("{0}")]
+pub struct RowError(Box<str>);
+
+impl de::Error for RowError {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        Self(msg.to_string().into())
+    }
+}
+
+/// Deserializes a single result row into `T`.
+///
+/// Variables map onto struct fields by name, so `#[serde(rename = "...")]`
+/// works as usual. An unbound variable is simply absent: declare the field as
+/// an [`Option`] or give it a `#[serde(default)]`.
+///
+/// Terms convert according to the requested field type:
+///
+/// - strings receive the lexical value of any term, including IRIs and blank node labels;
+/// - integers, floats and `bool` parse the lexical value of the literal with
+///   the same XSD rules as [`RDFTerm::to_typed`], including the range of
+///   derived integer types; an `xsd:decimal` read into a float is rounded to
+///   the nearest `f64`;
+/// - types that deserialize from strings receive the lexical value unchanged,
+///   so they only accept the forms their own parser does;
+/// - with the `chrono` or `time` feature, `xsd:date` and `xsd:dateTime`
+///   literals are read with the XSD rules first and handed to types other than
+///   [`String`] as `2024-01-01` and `2024-01-01T12:00:00.5+02:00`: a date
+///   drops its timezone, so chrono's `NaiveDate` and time's `Date` accept
+///   `2024-01-01Z`, and a date-time suits chrono's types and
+///   `time::serde::rfc3339`, although `DateTime<Utc>` still needs an offset;
+/// - [`RDFTerm`] receives the term itself, with its kind, language and datatype.
+///
+/// Self-describing targets such as `serde_json::Value` receive numeric and
+/// boolean literals as numbers and booleans, except `xsd:decimal`, which is
+/// passed as its lexical value to keep its precision.
+///
+/// For dates, times and durations with the XSD rules, including timezones,
+/// convert the term itself with [`RDFTerm::to_typed`] or one of its
+/// `TryFrom<&RDFTerm>` implementations.
+///
+/// ```
+/// use serde::Deserialize;
+/// use sparql_http_client::response::{from_row, RDFTerm, Row};
+///
+/// #[derive(Deserialize)]
+/// struct Country {
+///     #[serde(rename = "countryLabel")]
+///     label: String,
+///     population: Option<u64>,
+///     country: RDFTerm,
+/// }
+///
+/// let mut row = Row::new();
+/// row.insert("country".into(), "<http://www.wikidata.org/entity/Q142>".parse()?);
+/// row.insert("countryLabel".into(), r#""France"@en"#.parse()?);
+///
+/// let country: Country = from_row(&row)?;
+/// assert_eq!(country.label, "France");
+/// assert_eq!(country.population, None);
+/// assert!(country.country.is_iri());
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn from_row<'a, T: Deserialize<'a>>(row: &'a Row) -> Result<T, RowError> {
+    T::deserialize(RowDeserializer { row })
+}
+
+struct RowDeserializer<'a> {
+    row: &'a Row,
+}
+
+impl<'de> de::Deserializer<'de> for RowDeserializer<'de> {
+    type Error = RowError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, RowError> {
+        visitor.visit_map(RowAccess {
+            entries: self.row.iter(),
+            value: None,
+        })
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+struct RowAccess<'a, I> {
+    entries: I,
+    value: Option<&'a RDFTerm>,
+}
+
+impl<'de, I> MapAccess<'de> for RowAccess<'de, I>
+where
+    I: Iterator<Item = (&'de std::sync::Arc<str>, &'de RDFTerm)>,
+{
+    type Error = RowError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, RowError> {
+        let Some((var, term)) = self.entries.next() else {
+            return Ok(None);
+        };
+        self.value = Some(term);
+        seed.deserialize(de::value::BorrowedStrDeserializer::new(var))
+            .map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, RowError> {
+        let term = self
+            .value
+            .take()
+            .ok_or_else(|| de::Error::custom("value requested before key"))?;
+        seed.deserialize(TermDeserializer { term })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.entries.size_hint().1
+    }
+}
+
+/// Deserializes one bound term, converting its lexical value on demand.
+pub(super) struct TermDeserializer<'a> {
+    pub(super) term: &'a RDFTerm,
+}
+
+impl<'a> TermDeserializer<'a> {
+    fn value(&self) -> &'a str {
+        &self.term.value
+    }
+
+    /// Returns the value without the whitespace that XSD collapses.
+    fn lexical(&self) -> &'a str {
+        collapse(&self.term.value)
+    }
+
+    /// Returns the local name of an XSD datatype, e.g. `integer`.
+    fn xsd_type(&self) -> Option<&'a str> {
+        match &self.term.kind {
+            RDFType::Literal(LiteralType::Datatype(dt)) => dt.strip_prefix(XSD),
+            _ => None,
+        }
+    }
+
+    fn parse<T>(&self) -> Result<T, RowError>
+    where
+        T: std::str::FromStr,
+        T::Err: std::fmt::Display,
+    {
+        self.lexical()
+            .parse()
+            .map_err(|e| de::Error::custom(format_args!("invalid value {:?}: {e}", self.value())))
+    }
+
+    /// Checks that a literal of an XSD integer type is within the range of
+    /// that type.
+    fn check_integer_range(&self) -> Result<(), RowError> {
+        if let Some(name) = self.xsd_type().filter(|name| is_integer_type(name)) {
+            match parse_integer_of(name, self.lexical()) {
+                Ok(_) | Err(LiteralError::OutOfRange { target: "i64", .. }) => {}
+                Err(e) => return Err(de::Error::custom(e)),
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_integer<T>(&self) -> Result<T, RowError>
+    where
+        T: std::str::FromStr,
+        T::Err: std::fmt::Display,
+    {
+        self.check_integer_range()?;
+        self.parse()
+    }
+
+    fn parse_f64(&self) -> Result<f64, RowError> {
+        self.check_integer_range()?;
+        parse_double(self.lexical()).map_err(de::Error::custom)
+    }
+
+    fn parse_bool(&self) -> Result<bool, RowError> {
+        parse_boolean(self.lexical()).map_err(de::Error::custom)
+    }
+
+    /// Visits the lexical value, or the form of an `xsd:date` or
+    /// `xsd:dateTime` that chrono and time parse.
+    fn visit_value<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value, RowError> {
+        #[cfg(any(feature = "chrono", feature = "time"))]
+        if let Some(value) = self.temporal().filter(|value| value != self.value()) {
+            return visitor.visit_string(value);
+        }
+        visitor.visit_borrowed_str(self.value())
+    }
+
+    /// Writes a valid `xsd:date` as `YYYY-MM-DD` and a valid `xsd:dateTime`
+    /// as RFC 3339, with `Z` for UTC.
+    #[cfg(any(feature = "chrono", feature = "time"))]
+    fn temporal(&self) -> Option<String> {
+        let name = self.xsd_type()?;
+        if name == "date" {
+            let date = parse_date(self.lexical()).ok()?;
+            return Some(format_date(date.year, date.month, date.day));
+        }
+        if !is_date_time_type(name) {
+            return None;
+        }
+        let value = parse_date_time(self.lexical()).ok()?;
+        let mut out = format!(
+            "{}T{:02}:{:02}:{:02}",
+            format_date(value.year, value.month, value.day),
+            value.hour,
+            value.minute,
+            value.second
+        );
+        if value.nanosecond != 0 {
+            let fraction = format!("{:09}", value.nanosecond);
+            out.push('.');
+            out.push_str(fraction.trim_end_matches('0'));
+        }
+        match value.offset {
+            None => {}
+            Some(0) => out.push('Z'),
+            Some(offset) => {
+                let sign = if offset < 0 { '-' } else { '+' };
+                let offset = offset.unsigned_abs();
+                out.push_str(&format!("{sign}{:02}:{:02}", offset / 60, offset % 60));
+            }
+        }
+        Some(out)
+    }
+}
+
+/// Years outside `0000`–`9999` carry a sign, as chrono and ISO 8601 expect.
+#[cfg(any(feature = "chrono", feature = "time"))]
+fn format_date(year: i64, month: u8, day: u8) -> String {
+    if (0..=9999).contains(&year) {
+        format!("{year:04}-{month:02}-{day:02}")
+    } else {
+        format!("{year:+05}-{month:02}-{day:02}")
+    }
+}
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, RowError> {
+                visitor.$visit(self.parse_integer()?)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for TermDeserializer<'de> {
+    type Error = RowError;
+
+    /// Picks a value by datatype for self-describing targets such as
+    /// `serde_json::Value`; `xsd:decimal` and anything that is not a numeric or
+    /// boolean literal is passed on as a string, as for `&str`.
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, RowError> {
+        match self.xsd_type() {
+            Some("boolean") => visitor.visit_bool(self.parse_bool()?),
+            Some("double" | "float") => visitor.visit_f64(self.parse_f64()?),
+            Some("decimal") => visitor
+                .visit_borrowed_str(parse_decimal(self.lexical()).map_err(de::Error::custom)?),
+            Some(name) if is_integer_type(name) => match parse_integer_of(name, self.lexical()) {
+                Ok(v) => visitor.visit_i64(v),
+                Err(LiteralError::OutOfRange { target: "i64", .. }) => {
+                    match self.lexical().parse::<u64>() {
+                        Ok(v) => visitor.visit_u64(v),
+                        Err(_) => visitor.visit_borrowed_str(self.lexical()),
+                    }
+                }
+                Err(e) => Err(de::Error::custom(e)),
+            },
+            _ => self.visit_value(visitor),
+        }
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, RowError> {
+        visitor.visit_bool(self.parse_bool()?)
+    }
+
+    deserialize_parsed! {
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, RowError> {
+        visitor.visit_f32(self.parse_f64()? as f32)
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, RowError> {
+        visitor.visit_f64(self.parse_f64()?)
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, RowError> {
+        visitor.visit_char(self.parse()?)
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, RowError> {
+        self.visit_value(visitor)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, RowError> {
+        visitor.visit_borrowed_str(self.value())
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, RowError> {
+        visitor.visit_borrowed_bytes(self.value().as_bytes())
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, RowError> {
+        visitor.visit_borrowed_bytes(self.value().as_bytes())
+    }
+
+    /// Only bound variables reach this deserializer, so options are always `Some`.
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, RowError> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, RowError> {
+        if name == TERM_TOKEN {
+            visitor.visit_seq(TermParts::new(self.term))
+        } else {
+            visitor.visit_newtype_struct(self)
+        }
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, RowError> {
+        visitor.visit_enum(self.value().into_deserializer())
+    }
+
+    serde::forward_to_deserialize_any! {
+        unit unit_struct seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+/// Hands an [`RDFTerm`] over to its own `Deserialize` impl as its kind, value,
+/// annotation and inferred flag, with no formatting or parsing on the way.
+struct TermParts<'a> {
+    parts: std::vec::IntoIter<TermPart<'a>>,
+}
+
+enum TermPart<'a> {
+    Str(&'a str),
+    Term(&'a RDFTerm),
+    Bool(bool),
+}
+
+impl<'a> TermParts<'a> {
+    fn new(term: &'a RDFTerm) -> Self {
+        use TermPart::{Str, Term};
+
+        let value = Str(&term.value);
+        let mut parts = match &term.kind {
+            RDFType::IRI => vec![Str(TERM_IRI), value],
+            RDFType::BlankNode => vec![Str(TERM_BLANK_NODE), value],
+            RDFType::Literal(LiteralType::Plain) => vec![Str(TERM_PLAIN), value],
+            RDFType::Literal(LiteralType::Lang(lang)) => vec![Str(TERM_LANG), value, Str(lang)],
+            RDFType::Literal(LiteralType::DirLang(lang, direction)) => vec![
+                Str(TERM_DIR_LANG),
+                value,
+                Str(lang),
+                Str(direction.as_str()),
+            ],
+            RDFType::Literal(LiteralType::Datatype(datatype)) => {
+                vec![Str(TERM_DATATYPE), value, Str(datatype)]
+            }
+            RDFType::Triple(triple) => vec![
+                Str(TERM_TRIPLE),
+                value,
+                Term(&triple.subject),
+                Term(&triple.predicate),
+                Term(&triple.object),
+            ],
+        };
+        parts.push(TermPart::Bool(term.inferred));
+        Self {
+            parts: parts.into_iter(),
+        }
+    }
+}
+
+impl<'de> SeqAccess<'de> for TermParts<'de> {
+    type Error = RowError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, RowError> {
+        match self.parts.next() {
+            None => Ok(None),
+            Some(TermPart::Str(s)) => seed
+                .deserialize(de::value::BorrowedStrDeserializer::new(s))
+                .map(Some),
+            Some(TermPart::Term(term)) => seed.deserialize(TermDeserializer { term }).map(Some),
+            Some(TermPart::Bool(b)) => seed.deserialize(b.into_deserializer()).map(Some),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.parts.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use serde::Deserialize;
+
+    use super::*;
+
+    fn row(cells: &[(&str, &str)]) -> Row {
+        cells
+            .iter()
+            .map(|(var, cell)| (Into::into(*var), cell.parse().unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn struct_fields() {
+        #[derive(Debug, Deserialize)]
+        struct Item {
+            #[serde(rename = "itemLabel")]
+            label: String,
+            count: i64,
+            ratio: f64,
+            flag: bool,
+            missing: Option<u8>,
+            item: RDFTerm,
+        }
+
+        let row = row(&[
+            ("item", "<http://example.org/item>"),
+            ("itemLabel", r#""An \"item\""@en"#),
+            (
+                "count",
+                r#""-12"^^<http://www.w3.org/2001/XMLSchema#integer>"#,
+            ),
+            (
+                "ratio",
+                r#""1.5E0"^^<http://www.w3.org/2001/XMLSchema#double>"#,
+            ),
+            (
+                "flag",
+                r#""true"^^<http://www.w3.org/2001/XMLSchema#boolean>"#,
+            ),
+            ("extra", "_:b0"),
+        ]);
+
+        let item: Item = from_row(&row).unwrap();
+        assert_eq!(item.label, r#"An "item""#);
+        assert_eq!(item.count, -12);
+        assert_eq!(item.ratio, 1.5);
+        assert!(item.flag);
+        assert_eq!(item.missing, None);
+        assert_eq!(item.item, row["item"]);
+    }
+
+    #[test]
+    fn raw_terms_keep_annotations() {
+        let row = row(&[
+            ("label", r#""chat"@fr"#),
+            ("b", "_:node"),
+            ("odd", r#""x"^^<http://example.org/a b>"#),
+            (
+                "t",
+                r#"<< <http://ex.org/s> <http://ex.org/p> "1"^^<http://www.w3.org/2001/XMLSchema#integer> >>"#,
+            ),
+        ]);
+
+        let terms: HashMap<String, RDFTerm> = from_row(&row).unwrap();
+        assert_eq!(terms["label"], row["label"]);
+        assert_eq!(terms["b"], row["b"]);
+        assert_eq!(terms["odd"], row["odd"]);
+        assert_eq!(terms["t"], row["t"]);
+    }
+
+    #[test]
+    fn borrowed_and_self_describing() {
+        #[derive(Deserialize)]
+        struct Borrowed<'a> {
+            s: &'a str,
+        }
+
+        let row = row(&[
+            ("s", "<http://example.org/s>"),
+            ("n", r#""7"^^<http://www.w3.org/2001/XMLSchema#integer>"#),
+            (
+                "d",
+                r#""0.10000000000000000001"^^<http://www.w3.org/2001/XMLSchema#decimal>"#,
+            ),
+        ]);
+
+        assert_eq!(
+            from_row::<Borrowed>(&row).unwrap().s,
+            "http://example.org/s"
+        );
+        let value: serde_json::Value = from_row(&row).unwrap();
+        assert_eq!(value["n"], 7);
+        assert_eq!(value["d"], "0.10000000000000000001");
+    }
+
+    #[test]
+    fn invalid_number() {
+        #[derive(Debug, Deserialize)]
+        struct Item {
+            #[allow(dead_code)]
+            n: u32,
+        }
+
+        let err = from_row::<Item>(&row(&[("n", r#""many""#)])).unwrap_err();
+        assert!(err.to_string().contains("\"many\""), "{err}");
+    }
+
+    #[test]
+    fn xsd_lexical_rules() {
+        #[derive(Debug, Deserialize)]
+        struct Item {
+            n: i64,
+            x: f64,
+        }
+
+        let item: Item = from_row(&row(&[
+            ("n", "\"\\n+5 \"^^<http://www.w3.org/2001/XMLSchema#byte>"),
+            ("x", r#""-INF"^^<http://www.w3.org/2001/XMLSchema#double>"#),
+        ]))
+        .unwrap();
+        assert_eq!((item.n, item.x), (5, f64::NEG_INFINITY));
+
+        for cell in [
+            r#""-1"^^<http://www.w3.org/2001/XMLSchema#nonNegativeInteger>"#,
+            r#""inf"^^<http://www.w3.org/2001/XMLSchema#double>"#,
+            r#""infinity""#,
+        ] {
+            let row = row(&[("x", cell)]);
+            assert!(from_row::<HashMap<String, f64>>(&row).is_err(), "{cell}");
+        }
+        let row = row(&[("n", r#""1000"^^<http://www.w3.org/2001/XMLSchema#byte>"#)]);
+        assert!(from_row::<HashMap<String, i64>>(&row).is_err());
+        assert!(from_row::<serde_json::Value>(&row).is_err());
+    }
+
+    #[test]
+    fn missing_required_variable() {
+        #[derive(Debug, Deserialize)]
+        struct Item {
+            #[allow(dead_code)]
+            n: u32,
+        }
+
+        let err = from_row::<Item>(&Row::new()).unwrap_err();
+        assert!(err.to_string().contains("missing field `n`"), "{err}");
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_dates() {
+        use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, Utc};
+
+        #[derive(Debug, Deserialize)]
+        struct Item {
+            date: NaiveDate,
+            local: NaiveDateTime,
+            zoned: DateTime<FixedOffset>,
+            utc: DateTime<Utc>,
+            label: String,
+        }
+
+        let item: Item = from_row(&row(&[
+            (
+                "date",
+                r#""2024-01-01Z"^^<http://www.w3.org/2001/XMLSchema#date>"#,
+            ),
+            (
+                "local",
+                r#""2024-12-31T24:00:00"^^<http://www.w3.org/2001/XMLSchema#dateTime>"#,
+            ),
+            (
+                "zoned",
+                r#"" 2024-01-01T12:00:00.500-05:30 "^^<http://www.w3.org/2001/XMLSchema#dateTime>"#,
+            ),
+            (
+                "utc",
+                r#""2024-01-01T01:30:00+02:00"^^<http://www.w3.org/2001/XMLSchema#dateTime>"#,
+            ),
+            (
+                "label",
+                r#""2024-01-01Z"^^<http://www.w3.org/2001/XMLSchema#date>"#,
+            ),
+        ]))
+        .unwrap();
+        assert_eq!(item.date, NaiveDate::from_ymd_opt(2024, 1, 1).unwrap());
+        assert_eq!(item.local.to_string(), "2025-01-01 00:00:00");
+        assert_eq!(item.zoned.to_rfc3339(), "2024-01-01T12:00:00.500-05:30");
+        assert_eq!(item.utc.to_rfc3339(), "2023-12-31T23:30:00+00:00");
+        assert_eq!(item.label, "2024-01-01Z");
+
+        let value: serde_json::Value = from_row(&row(&[(
+            "d",
+            r#""-12345-06-07+01:00"^^<http://www.w3.org/2001/XMLSchema#date>"#,
+        )]))
+        .unwrap();
+        assert_eq!(value["d"], "-12345-06-07");
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn time_dates() {
+        #[derive(Debug, Deserialize)]
+        struct Item {
+            date: time::Date,
+        }
+
+        let item: Item = from_row(&row(&[(
+            "date",
+            r#""2024-03-01-01:00"^^<http://www.w3.org/2001/XMLSchema#date>"#,
+        )]))
+        .unwrap();
+        assert_eq!(
+            item.date,
+            time::Date::from_calendar_date(2024, time::Month::March, 1).unwrap()
+        );
+    }
+}
@@ -0,0 +1,199 @@
+use std::collections::{HashMap, HashSet};
+
+use super::term::RDFTerm;
+use super::triple::Triple;
+
+/// The response to a SPARQL DESCRIBE query, grouped by described resource.
+///
+/// Returned by [`SparqlQuery<DescribeQueryString>::run`](crate::SparqlQuery::run).
+/// Unlike [`ConstructQueryResponse`](super::ConstructQueryResponse), the whole
+/// graph is collected before returning so that triples can be grouped.
+///
+/// Each IRI subject forms one [`DescribedResource`]. Triples whose subject is a
+/// blank node are attached to the resource that references that blank node
+/// (directly or through other blank nodes), following the shape of a
+/// [Concise Bounded Description](https://www.w3.org/submission/CBD/).
+/// Blank nodes that no resource references form their own group.
+///
+/// # Example
+///
+/// ```
+/// use sparql_http_client::response::{DescribeQueryResponse, RDFTerm, RDFType, Triple};
+///
+/// let iri = |s: &str| RDFTerm { value: s.into(), kind: RDFType::IRI, inferred: false };
+/// let response = DescribeQueryResponse::from_triples(vec![Triple {
+///     subject: iri("http://example.org/alice"),
+///     predicate: iri("http://xmlns.com/foaf/0.1/knows"),
+///     object: iri("http://example.org/bob"),
+/// }]);
+///
+/// let alice = response.get("http://example.org/alice").unwrap();
+/// assert_eq!(alice.triples.len(), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DescribeQueryResponse {
+    resources: Vec<DescribedResource>,
+}
+
+/// All triples returned about one resource of a DESCRIBE query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DescribedResource {
+    /// The described resource, an IRI or an unreferenced blank node.
+    pub subject: RDFTerm,
+    /// The triples about `subject`, including those about the blank nodes it owns.
+    pub triples: Vec<Triple>,
+}
+
+impl DescribeQueryResponse {
+    /// Groups `triples` by described resource, preserving the order in which
+    /// resources first appear.
+    pub fn from_triples(triples: Vec<Triple>) -> Self {
+        // Maps each blank node to the first subject that references it.
+        let mut parents: HashMap<&RDFTerm, &RDFTerm> = HashMap::new();
+        for triple in &triples {
+            if triple.object.is_blank_node() && triple.object != triple.subject {
+                parents.entry(&triple.object).or_insert(&triple.subject);
+            }
+        }
+
+        let owners: Vec<RDFTerm> = triples
+            .iter()
+            .map(|triple| owner(&triple.subject, &parents).clone())
+            .collect();
+
+        let mut index: HashMap<RDFTerm, usize> = HashMap::new();
+        let mut resources: Vec<DescribedResource> = Vec::new();
+        for (triple, subject) in triples.into_iter().zip(owners) {
+            let i = *index.entry(subject.clone()).or_insert_with(|| {
+                resources.push(DescribedResource {
+                    subject,
+                    triples: Vec::new(),
+                });
+                resources.len() - 1
+            });
+            resources[i].triples.push(triple);
+        }
+
+        Self { resources }
+    }
+
+    /// Returns the described resources in the order they first appeared.
+    pub fn resources(&self) -> &[DescribedResource] {
+        &self.resources
+    }
+
+    /// Returns the resource whose subject IRI is `iri`, if any triples were returned about it.
+    pub fn get(&self, iri: &str) -> Option<&DescribedResource> {
+        self.resources
+            .iter()
+            .find(|r| r.subject.is_iri() && &*r.subject.value == iri)
+    }
+
+    /// Consumes this value and returns the described resources.
+    pub fn into_resources(self) -> Vec<DescribedResource> {
+        self.resources
+    }
+
+    /// Consumes this value and returns every triple, ungrouped.
+    pub fn into_triples(self) -> impl Iterator<Item = Triple> {
+        self.resources.into_iter().flat_map(|r| r.triples)
+    }
+}
+
+/// Follows blank-node references up to the owning resource.
+///
+/// Stops at the first non-blank subject, at an unreferenced blank node, or when
+/// the chain loops back on itself through a cycle of blank nodes.
+fn owner<'a>(subject: &'a RDFTerm, parents: &HashMap<&'a RDFTerm, &'a RDFTerm>) -> &'a RDFTerm {
+    let mut visited = HashSet::new();
+    let mut current = subject;
+    while current.is_blank_node() && visited.insert(current) {
+        match parents.get(current) {
+            Some(&parent) => current = parent,
+            None => break,
+        }
+    }
+    current
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::response::{LiteralType, RDFType};
+
+    fn iri(s: &str) -> RDFTerm {
+        RDFTerm {
+            value: s.into(),
+            kind: RDFType::IRI,
+            inferred: false,
+        }
+    }
+
+    fn bnode(s: &str) -> RDFTerm {
+        RDFTerm {
+            value: s.into(),
+            kind: RDFType::BlankNode,
+            inferred: false,
+        }
+    }
+
+    fn literal(s: &str) -> RDFTerm {
+        RDFTerm {
+            value: s.into(),
+            kind: RDFType::Literal(LiteralType::Plain),
+            inferred: false,
+        }
+    }
+
+    fn triple(subject: RDFTerm, predicate: &str, object: RDFTerm) -> Triple {
+        Triple {
+            subject,
+            predicate: iri(predicate),
+            object,
+        }
+    }
+
+    #[test]
+    fn groups_by_subject() {
+        let response = DescribeQueryResponse::from_triples(vec![
+            triple(iri("ex:a"), "ex:p", literal("1")),
+            triple(iri("ex:b"), "ex:p", literal("2")),
+            triple(iri("ex:a"), "ex:q", iri("ex:b")),
+        ]);
+
+        let subjects: Vec<_> = response
+            .resources()
+            .iter()
+            .map(|r| &*r.subject.value)
+            .collect();
+        assert_eq!(subjects, ["ex:a", "ex:b"]);
+        assert_eq!(response.get("ex:a").unwrap().triples.len(), 2);
+        assert_eq!(response.get("ex:b").unwrap().triples.len(), 1);
+    }
+
+    #[test]
+    fn attaches_blank_nodes_to_owner() {
+        let response = DescribeQueryResponse::from_triples(vec![
+            triple(bnode("b1"), "ex:street", literal("Main St")),
+            triple(bnode("b1"), "ex:geo", bnode("b2")),
+            triple(bnode("b2"), "ex:lat", literal("1.0")),
+            triple(iri("ex:a"), "ex:address", bnode("b1")),
+            triple(bnode("orphan"), "ex:p", literal("x")),
+        ]);
+
+        assert_eq!(response.resources().len(), 2);
+        assert_eq!(response.get("ex:a").unwrap().triples.len(), 4);
+        assert_eq!(response.resources()[1].subject, bnode("orphan"));
+    }
+
+    #[test]
+    fn blank_node_cycle() {
+        let response = DescribeQueryResponse::from_triples(vec![
+            triple(bnode("b1"), "ex:p", bnode("b2")),
+            triple(bnode("b2"), "ex:p", bnode("b3")),
+            triple(bnode("b3"), "ex:p", bnode("b2")),
+        ]);
+
+        assert_eq!(response.into_triples().count(), 3);
+    }
+}
@@ -0,0 +1,234 @@
+use reqwest::header::{HeaderValue, CONTENT_TYPE};
+use reqwest::Response;
+
+use super::select::StreamError;
+
+/// A SPARQL query results serialization that SELECT responses can be parsed from.
+///
+/// Choose the preferred format for every query of an endpoint with
+/// [`Endpoint::with_result_format`](crate::Endpoint::with_result_format), or for a
+/// single query with [`SparqlQuery::with_result_format`](crate::SparqlQuery::with_result_format).
+///
+/// The preferred format is listed first in a weighted `Accept` header that also
+/// covers every other supported format, and the response is parsed according to
+/// the `Content-Type` the endpoint actually returns. A response in any other
+/// media type fails with [`StreamError::UnsupportedContentType`].
+///
+/// All formats produce the same [`Row`](super::Row) values, although CSV cannot
+/// tell term kinds apart reliably (see [`is_lossy`](ResultFormat::is_lossy)).
+/// ASK queries prefer JSON when TSV or CSV is selected, since neither has a
+/// boolean encoding.
+///
+/// The `Xml` variant requires the `xml` cargo feature. The enum is
+/// non-exhaustive so that enabling the feature anywhere in the dependency graph
+/// cannot break a `match` on it.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ResultFormat {
+    /// [SPARQL 1.1 Query Results TSV](https://www.w3.org/TR/sparql11-results-csv-tsv/),
+    /// `text/tab-separated-values`.
+    #[default]
+    Tsv,
+    /// [SPARQL 1.1 Query Results JSON](https://www.w3.org/TR/sparql11-results-json/),
+    /// `application/sparql-results+json`.
+    Json,
+    /// [SPARQL 1.1 Query Results CSV](https://www.w3.org/TR/sparql11-results-csv-tsv/),
+    /// `text/csv`.
+    ///
+    /// CSV drops datatypes and language tags, and writes IRIs, blank nodes and
+    /// literals without any markers, so the kind of every term is inferred and
+    /// marked with [`RDFTerm::inferred`](super::RDFTerm::inferred).
+    Csv,
+    /// [SPARQL Query Results XML](https://www.w3.org/TR/rdf-sparql-XMLres/),
+    /// `application/sparql-results+xml`.
+    #[cfg(feature = "xml")]
+    Xml,
+}
+
+impl ResultFormat {
+    /// Every format that SELECT results can be parsed from, in the order of
+    /// preference used after the caller's own choice.
+    ///
+    /// CSV comes last since it loses term types.
+    const SELECT: &'static [Self] = &[
+        Self::Tsv,
+        Self::Json,
+        #[cfg(feature = "xml")]
+        Self::Xml,
+        Self::Csv,
+    ];
+
+    /// Every format that ASK results can be parsed from.
+    const ASK: &'static [Self] = &[
+        Self::Json,
+        #[cfg(feature = "xml")]
+        Self::Xml,
+    ];
+
+    /// Returns the media type sent in the `Accept` header for this format.
+    pub fn media_type(self) -> &'static str {
+        match self {
+            Self::Tsv => "text/tab-separated-values",
+            Self::Json => "application/sparql-results+json",
+            Self::Csv => "text/csv",
+            #[cfg(feature = "xml")]
+            Self::Xml => "application/sparql-results+xml",
+        }
+    }
+
+    /// Returns `true` if this format loses RDF term information.
+    ///
+    /// Only [`Csv`](ResultFormat::Csv) is lossy: its terms are classified by
+    /// [`SelectQueryResponse`](super::SelectQueryResponse) with a heuristic, so a
+    /// literal whose text looks like an absolute IRI comes back as an IRI, and
+    /// every literal is [`Plain`](super::LiteralType::Plain).
+    pub fn is_lossy(self) -> bool {
+        matches!(self, Self::Csv)
+    }
+
+    /// Returns the format whose media type matches `content_type`, ignoring
+    /// parameters such as `charset` and letter case.
+    ///
+    /// `application/json` is accepted as an alias for
+    /// [`Json`](ResultFormat::Json), since some endpoints label SPARQL JSON
+    /// results with the generic type.
+    ///
+    /// ```
+    /// use sparql_http_client::ResultFormat;
+    ///
+    /// assert_eq!(
+    ///     ResultFormat::from_media_type("text/tab-separated-values; charset=utf-8"),
+    ///     Some(ResultFormat::Tsv),
+    /// );
+    /// assert_eq!(ResultFormat::from_media_type("text/html"), None);
+    /// ```
+    pub fn from_media_type(content_type: &str) -> Option<Self> {
+        let essence = content_type.split(';').next().unwrap_or_default().trim();
+        if essence.eq_ignore_ascii_case("application/json") {
+            return Some(Self::Json);
+        }
+        Self::SELECT
+            .iter()
+            .copied()
+            .find(|format| essence.eq_ignore_ascii_case(format.media_type()))
+    }
+
+    /// Builds the `Accept` header for a SELECT query preferring this format.
+    pub(crate) fn select_accept(self) -> HeaderValue {
+        accept(self, Self::SELECT)
+    }
+
+    /// Builds the `Accept` header for an ASK query preferring this format.
+    pub(crate) fn ask_accept(self) -> HeaderValue {
+        accept(self, Self::ASK)
+    }
+
+    /// Picks the parser for a SELECT response from its `Content-Type`.
+    pub(crate) fn negotiated_select(self, response: &Response) -> Result<Self, StreamError> {
+        negotiated(self, response, Self::SELECT)
+    }
+
+    /// Picks the parser for an ASK response from its `Content-Type`.
+    pub(crate) fn negotiated_ask(self, response: &Response) -> Result<Self, StreamError> {
+        negotiated(self, response, Self::ASK)
+    }
+}
+
+/// Lists `preferred` first, then the rest of `formats` with decreasing weights.
+///
+/// `preferred` is assumed to be in `formats`; otherwise the first of `formats` leads.
+fn accept(preferred: ResultFormat, formats: &[ResultFormat]) -> HeaderValue {
+    let preferred = if formats.contains(&preferred) {
+        preferred
+    } else {
+        formats[0]
+    };
+    let mut accept = preferred.media_type().to_owned();
+    let rest = formats.iter().filter(|&&format| format != preferred);
+    for (format, q) in rest.zip((1..=9).rev()) {
+        accept.push_str(&format!(", {};q=0.{q}", format.media_type()));
+    }
+    HeaderValue::from_str(&accept).expect("valid characters")
+}
+
+/// Returns the format named by the response's `Content-Type`, or `requested`
+/// if the endpoint sent none.
+fn negotiated(
+    requested: ResultFormat,
+    response: &Response,
+    formats: &[ResultFormat],
+) -> Result<ResultFormat, StreamError> {
+    let Some(content_type) = response.headers().get(CONTENT_TYPE) else {
+        return Ok(requested);
+    };
+    let content_type = String::from_utf8_lossy(content_type.as_bytes());
+    ResultFormat::from_media_type(&content_type)
+        .filter(|format| formats.contains(format))
+        .ok_or_else(|| StreamError::UnsupportedContentType(content_type.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(content_type: Option<&str>) -> Response {
+        let mut builder = http::Response::builder();
+        if let Some(content_type) = content_type {
+            builder = builder.header(CONTENT_TYPE, content_type);
+        }
+        builder.body("").unwrap().into()
+    }
+
+    #[test]
+    fn accept_lists_preferred_first() {
+        let accept = ResultFormat::Json.select_accept();
+        let accept = accept.to_str().unwrap();
+        assert!(
+            accept.starts_with("application/sparql-results+json, text/tab-separated-values;q=0.9"),
+            "{accept}"
+        );
+        // CSV is lossy, so it is always offered last.
+        assert!(accept
+            .rsplit(", ")
+            .next()
+            .unwrap()
+            .starts_with("text/csv;q="));
+    }
+
+    #[test]
+    fn ask_accept_skips_tabular_formats() {
+        let accept = ResultFormat::Tsv.ask_accept();
+        let accept = accept.to_str().unwrap();
+        assert!(
+            accept.starts_with("application/sparql-results+json"),
+            "{accept}"
+        );
+        assert!(!accept.contains("text/"), "{accept}");
+    }
+
+    #[test]
+    fn negotiate_from_content_type() {
+        let requested = ResultFormat::Tsv;
+        let format = requested
+            .negotiated_select(&response(Some(
+                "Application/SPARQL-Results+JSON;charset=UTF-8",
+            )))
+            .unwrap();
+        assert_eq!(format, ResultFormat::Json);
+        assert_eq!(
+            requested.negotiated_select(&response(None)).unwrap(),
+            requested
+        );
+    }
+
+    #[test]
+    fn reject_unsupported_content_type() {
+        let err = ResultFormat::Json
+            .negotiated_ask(&response(Some("text/csv")))
+            .unwrap_err();
+        assert!(
+            matches!(&err, StreamError::UnsupportedContentType(t) if &**t == "text/csv"),
+            "{err:?}"
+        );
+    }
+}
@@ -0,0 +1,304 @@
+use oxrdf::{BlankNode, Literal, NamedNode, NamedOrBlankNode, Term};
+use thiserror::Error;
+
+use super::term::{BaseDirection, LiteralType, RDFTerm, RDFType};
+use super::triple::Triple;
+
+/// Error returned when converting an [`RDFTerm`] into an [`oxrdf`] term fails.
+///
+/// `oxrdf` validates what results formats leave unchecked, such as IRIs and
+/// blank node labels.
+#[derive(Debug, Error)]
+pub enum TermConversionError {
+    /// The term is not of the kind the target type holds, e.g. a literal
+    /// converted into a [`NamedNode`].
+    #[error("expected {expected}, found {found}")]
+    UnexpectedKind {
+        expected: &'static str,
+        found: Box<str>,
+    },
+    /// The IRI of the term or of its datatype is not valid.
+    #[error(transparent)]
+    Iri(#[from] oxrdf::IriParseError),
+    /// The blank node label is not valid.
+    #[error(transparent)]
+    BlankNode(#[from] oxrdf::BlankNodeIdParseError),
+    /// The language tag is not well-formed.
+    #[error(transparent)]
+    LanguageTag(#[from] oxrdf::LanguageTagParseError),
+    /// The term is an RDF 1.2 construct that `oxrdf` only models with the
+    /// `sparql-12` feature.
+    #[error("{0} need the `sparql-12` feature")]
+    Unsupported(&'static str),
+}
+
+impl From<NamedNode> for RDFTerm {
+    fn from(node: NamedNode) -> Self {
+        RDFTerm::iri(node.into_string())
+    }
+}
+
+impl From<BlankNode> for RDFTerm {
+    fn from(node: BlankNode) -> Self {
+        RDFTerm {
+            value: node.into_string().into(),
+            kind: RDFType::BlankNode,
+            inferred: false,
+        }
+    }
+}
+
+/// `xsd:string` literals become [`LiteralType::Plain`], as `oxrdf` does not
+/// tell them apart.
+impl From<Literal> for RDFTerm {
+    fn from(literal: Literal) -> Self {
+        let kind = match (literal.language(), literal.datatype()) {
+            (Some(lang), _) => match direction(&literal) {
+                Some(direction) => LiteralType::DirLang(lang.into(), direction),
+                None => LiteralType::Lang(lang.into()),
+            },
+            (None, dt) if dt == oxrdf::vocab::xsd::STRING => LiteralType::Plain,
+            (None, dt) => LiteralType::Datatype(dt.as_str().into()),
+        };
+        RDFTerm {
+            value: literal.value().into(),
+            kind: RDFType::Literal(kind),
+            inferred: false,
+        }
+    }
+}
+
+#[cfg(feature = "sparql-12")]
+fn direction(literal: &Literal) -> Option<BaseDirection> {
+    literal.direction().map(BaseDirection::from)
+}
+
+/// Base directions exist whenever any crate enables oxrdf's `rdf-12` feature,
+/// so they are read back from the literal's N-Triples form, which keeps
+/// `@lang--dir`.
+#[cfg(not(feature = "sparql-12"))]
+fn direction(literal: &Literal) -> Option<BaseDirection> {
+    literal.to_string().parse::<RDFTerm>().ok()?.direction()
+}
+
+impl From<NamedOrBlankNode> for RDFTerm {
+    fn from(node: NamedOrBlankNode) -> Self {
+        match node {
+            NamedOrBlankNode::NamedNode(node) => node.into(),
+            NamedOrBlankNode::BlankNode(node) => node.into(),
+        }
+    }
+}
+
+impl From<Term> for RDFTerm {
+    fn from(term: Term) -> Self {
+        #[allow(unreachable_patterns)]
+        match term {
+            Term::NamedNode(node) => node.into(),
+            Term::BlankNode(node) => node.into(),
+            Term::Literal(literal) => literal.into(),
+            // Triple terms exist whenever any crate enables oxrdf's `rdf-12`
+            // feature, so they are read back from their N-Triples form rather
+            // than matched by name.
+            other => other
+                .to_string()
+                .parse()
+                .expect("oxrdf writes valid N-Triples terms"),
+        }
+    }
+}
+
+impl From<oxrdf::Triple> for Triple {
+    fn from(triple: oxrdf::Triple) -> Self {
+        Triple {
+            subject: triple.subject.into(),
+            predicate: triple.predicate.into(),
+            object: triple.object.into(),
+        }
+    }
+}
+
+impl TryFrom<&RDFTerm> for NamedNode {
+    type Error = TermConversionError;
+
+    fn try_from(term: &RDFTerm) -> Result<Self, Self::Error> {
+        match term.kind {
+            RDFType::IRI => Ok(NamedNode::new(&*term.value)?),
+            _ => Err(unexpected_kind("an IRI", term)),
+        }
+    }
+}
+
+impl TryFrom<&RDFTerm> for BlankNode {
+    type Error = TermConversionError;
+
+    fn try_from(term: &RDFTerm) -> Result<Self, Self::Error> {
+        match term.kind {
+            RDFType::BlankNode => Ok(BlankNode::new(&*term.value)?),
+            _ => Err(unexpected_kind("a blank node", term)),
+        }
+    }
+}
+
+impl TryFrom<&RDFTerm> for Literal {
+    type Error = TermConversionError;
+
+    fn try_from(term: &RDFTerm) -> Result<Self, Self::Error> {
+        let value = &*term.value;
+        match &term.kind {
+            RDFType::Literal(LiteralType::Plain) => Ok(Literal::new_simple_literal(value)),
+            RDFType::Literal(LiteralType::Lang(lang)) => {
+                Ok(Literal::new_language_tagged_literal(value, &**lang)?)
+            }
+            #[cfg(feature = "sparql-12")]
+            RDFType::Literal(LiteralType::DirLang(lang, direction)) => Ok(
+                Literal::new_directional_language_tagged_literal(value, &**lang, *direction)?,
+            ),
+            #[cfg(not(feature = "sparql-12"))]
+            RDFType::Literal(LiteralType::DirLang(..)) => Err(TermConversionError::Unsupported(
+                "directional language tags",
+            )),
+            RDFType::Literal(LiteralType::Datatype(dt)) => {
+                Ok(Literal::new_typed_literal(value, NamedNode::new(&**dt)?))
+            }
+            _ => Err(unexpected_kind("a literal", term)),
+        }
+    }
+}
+
+impl TryFrom<&RDFTerm> for NamedOrBlankNode {
+    type Error = TermConversionError;
+
+    fn try_from(term: &RDFTerm) -> Result<Self, Self::Error> {
+        match term.kind {
+            RDFType::IRI => Ok(NamedNode::try_from(term)?.into()),
+            RDFType::BlankNode => Ok(BlankNode::try_from(term)?.into()),
+            _ => Err(unexpected_kind("an IRI or a blank node", term)),
+        }
+    }
+}
+
+impl TryFrom<&RDFTerm> for Term {
+    type Error = TermConversionError;
+
+    fn try_from(term: &RDFTerm) -> Result<Self, Self::Error> {
+        match &term.kind {
+            RDFType::IRI => Ok(NamedNode::try_from(term)?.into()),
+            RDFType::BlankNode => Ok(BlankNode::try_from(term)?.into()),
+            RDFType::Literal(_) => Ok(Literal::try_from(term)?.into()),
+            #[cfg(feature = "sparql-12")]
+            RDFType::Triple(triple) => Ok(oxrdf::Triple::try_from(&**triple)?.into()),
+            #[cfg(not(feature = "sparql-12"))]
+            RDFType::Triple(_) => Err(TermConversionError::Unsupported("triple terms")),
+        }
+    }
+}
+
+impl TryFrom<&Triple> for oxrdf::Triple {
+    type Error = TermConversionError;
+
+    fn try_from(triple: &Triple) -> Result<Self, Self::Error> {
+        Ok(oxrdf::Triple::new(
+            NamedOrBlankNode::try_from(&triple.subject)?,
+            NamedNode::try_from(&triple.predicate)?,
+            Term::try_from(&triple.object)?,
+        ))
+    }
+}
+
+macro_rules! owned_conversions {
+    ($($t:ty),*) => {
+        $(
+            impl TryFrom<RDFTerm> for $t {
+                type Error = TermConversionError;
+
+                fn try_from(term: RDFTerm) -> Result<Self, Self::Error> {
+                    Self::try_from(&term)
+                }
+            }
+        )*
+    };
+}
+
+owned_conversions!(NamedNode, BlankNode, Literal, NamedOrBlankNode, Term);
+
+impl TryFrom<Triple> for oxrdf::Triple {
+    type Error = TermConversionError;
+
+    fn try_from(triple: Triple) -> Result<Self, Self::Error> {
+        Self::try_from(&triple)
+    }
+}
+
+fn unexpected_kind(expected: &'static str, term: &RDFTerm) -> TermConversionError {
+    TermConversionError::UnexpectedKind {
+        expected,
+        found: term.to_string().into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() -> anyhow::Result<()> {
+        for term in [
+            RDFTerm::iri("http://example.org/a"),
+            RDFTerm::from(BlankNode::new("b0")?),
+            RDFTerm::literal("plain"),
+            RDFTerm::lang_literal("chat", "fr"),
+            RDFTerm::from(42),
+        ] {
+            assert_eq!(RDFTerm::from(Term::try_from(&term)?), term);
+        }
+        assert_eq!(
+            Literal::try_from(RDFTerm::lang_literal("hello", "en-GB"))?,
+            Literal::new_language_tagged_literal("hello", "en-gb")?
+        );
+
+        let triple = Triple {
+            subject: RDFTerm::iri("http://example.org/s"),
+            predicate: RDFTerm::iri("http://example.org/p"),
+            object: RDFTerm::literal("o"),
+        };
+        assert_eq!(Triple::from(oxrdf::Triple::try_from(&triple)?), triple);
+
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_terms() {
+        assert!(matches!(
+            NamedNode::try_from(RDFTerm::literal("a")),
+            Err(TermConversionError::UnexpectedKind { .. })
+        ));
+        assert!(matches!(
+            NamedNode::try_from(RDFTerm::iri("not absolute")),
+            Err(TermConversionError::Iri(_))
+        ));
+        assert!(matches!(
+            BlankNode::try_from(&"_:a b".parse::<RDFTerm>().unwrap()),
+            Err(TermConversionError::BlankNode(_))
+        ));
+        let triple = RDFTerm::triple(
+            RDFTerm::literal("s"),
+            RDFTerm::iri("http://example.org/p"),
+            RDFTerm::literal("o"),
+        );
+        assert!(Term::try_from(&triple).is_err());
+    }
+
+    #[cfg(feature = "sparql-12")]
+    #[test]
+    fn rdf_12_terms() -> anyhow::Result<()> {
+        let term = RDFTerm::triple(
+            RDFTerm::iri("http://example.org/s"),
+            RDFTerm::iri("http://example.org/p"),
+            RDFTerm::dir_lang_literal("مرحبا", "ar", BaseDirection::Rtl),
+        );
+        assert_eq!(RDFTerm::from(Term::try_from(&term)?), term);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,491 @@
+use std::collections::{HashMap, VecDeque};
+use std::ops::Range;
+use std::sync::Arc;
+
+use futures_util::{stream, Stream, StreamExt};
+use serde::{de::DeserializeOwned, Deserialize};
+
+use super::select::{ParseError, Row, RowStream, StreamError};
+use super::term::{lang_literal_type, LiteralType, RDFTerm, RDFType};
+
+/// The `head` object of a SPARQL JSON results document.
+#[derive(Debug, Default, Deserialize)]
+pub(super) struct JsonHead {
+    #[serde(default)]
+    pub vars: Vec<Box<str>>,
+    pub link: Option<Box<[Box<str>]>>,
+}
+
+/// A single RDF term as encoded in SPARQL JSON results.
+#[derive(Debug, Deserialize)]
+struct JsonTerm {
+    #[serde(rename = "type")]
+    kind: Box<str>,
+    value: JsonValue,
+    #[serde(rename = "xml:lang")]
+    lang: Option<Box<str>>,
+    #[serde(rename = "its:dir")]
+    direction: Option<Box<str>>,
+    datatype: Option<Box<str>>,
+}
+
+/// The `value` of a term: a string, or an object for a `triple` term.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum JsonValue {
+    String(Box<str>),
+    Triple(Box<JsonTriple>),
+}
+
+/// The parts of a triple term, each encoded like any other term.
+#[derive(Debug, Deserialize)]
+struct JsonTriple {
+    subject: JsonTerm,
+    predicate: JsonTerm,
+    object: JsonTerm,
+}
+
+impl JsonTerm {
+    fn into_term(self) -> Result<RDFTerm, ParseError> {
+        let value = match self.value {
+            JsonValue::Triple(triple) if &*self.kind == "triple" => {
+                let JsonTriple {
+                    subject,
+                    predicate,
+                    object,
+                } = *triple;
+                return Ok(RDFTerm::triple(
+                    subject.into_term()?,
+                    predicate.into_term()?,
+                    object.into_term()?,
+                ));
+            }
+            JsonValue::Triple(_) => return Err(ParseError::UnsupportedTerm(self.kind)),
+            JsonValue::String(value) => value,
+        };
+        let kind = match (&*self.kind, self.lang, self.datatype) {
+            ("uri", ..) => RDFType::IRI,
+            ("bnode", ..) => RDFType::BlankNode,
+            // `typed-literal` comes from the 2008 W3C Note and is still emitted by some stores.
+            ("literal" | "typed-literal", Some(lang), _) => {
+                RDFType::Literal(lang_literal_type(&lang, self.direction.as_deref())?)
+            }
+            ("literal" | "typed-literal", None, Some(dt)) => {
+                RDFType::Literal(LiteralType::Datatype(dt))
+            }
+            ("literal" | "typed-literal", None, None) => RDFType::Literal(LiteralType::Plain),
+            (other, ..) => return Err(ParseError::UnsupportedTerm(other.into())),
+        };
+        Ok(RDFTerm {
+            value,
+            kind,
+            inferred: false,
+        })
+    }
+}
+
+type JsonBinding = HashMap<Box<str>, JsonTerm>;
+
+enum Event {
+    Head(JsonHead),
+    Binding(JsonBinding),
+}
+
+/// Where the reader is in the document structure.
+#[derive(Debug, Clone, Copy)]
+enum State {
+    Start,
+    Top,
+    Results,
+    Bindings,
+    Done,
+}
+
+/// An incremental reader over a SPARQL JSON results document.
+///
+/// Only the skeleton of the document is scanned by hand; `head` and each
+/// binding object are buffered until complete and then handed to
+/// `serde_json`, so memory use is bounded by the largest single binding.
+struct JsonEvents<S> {
+    stream: S,
+    buf: Vec<u8>,
+    pos: usize,
+    eof: bool,
+    state: State,
+}
+
+impl<S, B> JsonEvents<S>
+where
+    S: Stream<Item = Result<B, reqwest::Error>> + Unpin,
+    B: AsRef<[u8]>,
+{
+    fn new(stream: S) -> Self {
+        Self {
+            stream,
+            buf: Vec::new(),
+            pos: 0,
+            eof: false,
+            state: State::Start,
+        }
+    }
+
+    async fn next_event(&mut self) -> Result<Option<Event>, StreamError> {
+        loop {
+            match self.state {
+                State::Start => {
+                    self.expect(b'{').await?;
+                    self.state = State::Top;
+                }
+                State::Top => {
+                    if !self.next_member(b'}').await? {
+                        self.state = State::Done;
+                        continue;
+                    }
+                    match &*self.key().await? {
+                        "head" => return Ok(Some(Event::Head(self.parse().await?))),
+                        "results" => {
+                            self.expect(b'{').await?;
+                            self.state = State::Results;
+                        }
+                        _ => {
+                            self.value().await?;
+                        }
+                    }
+                }
+                State::Results => {
+                    if !self.next_member(b'}').await? {
+                        self.state = State::Top;
+                        continue;
+                    }
+                    if &*self.key().await? == "bindings" {
+                        self.expect(b'[').await?;
+                        self.state = State::Bindings;
+                    } else {
+                        self.value().await?;
+                    }
+                }
+                State::Bindings => {
+                    if !self.next_member(b']').await? {
+                        self.state = State::Results;
+                        continue;
+                    }
+                    return Ok(Some(Event::Binding(self.parse().await?)));
+                }
+                State::Done => return Ok(None),
+            }
+        }
+    }
+
+    /// Reads the next chunk into the buffer. Returns `false` at end of stream.
+    async fn fill(&mut self) -> Result<bool, StreamError> {
+        if self.eof {
+            return Ok(false);
+        }
+        if self.pos > 0 && self.pos * 2 >= self.buf.len() {
+            self.buf.drain(..self.pos);
+            self.pos = 0;
+        }
+        match self.stream.next().await {
+            Some(chunk) => {
+                self.buf.extend_from_slice(chunk?.as_ref());
+                Ok(true)
+            }
+            None => {
+                self.eof = true;
+                Ok(false)
+            }
+        }
+    }
+
+    /// Skips whitespace and returns the next byte without consuming it.
+    async fn peek(&mut self) -> Result<u8, StreamError> {
+        loop {
+            while let Some(&b) = self.buf.get(self.pos) {
+                if !b.is_ascii_whitespace() {
+                    return Ok(b);
+                }
+                self.pos += 1;
+            }
+            if !self.fill().await? {
+                return Err(syntax_error("unexpected end of JSON results"));
+            }
+        }
+    }
+
+    async fn expect(&mut self, byte: u8) -> Result<(), StreamError> {
+        if self.peek().await? != byte {
+            return Err(syntax_error(&format!("expected `{}`", byte as char)));
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    /// Consumes a separating `,` if present. Returns `false` after consuming `close`.
+    async fn next_member(&mut self, close: u8) -> Result<bool, StreamError> {
+        let mut b = self.peek().await?;
+        if b == b',' {
+            self.pos += 1;
+            b = self.peek().await?;
+        }
+        if b == close {
+            self.pos += 1;
+            return Ok(false);
+        }
+        Ok(true)
+    }
+
+    /// Reads an object key and the `:` that follows it.
+    async fn key(&mut self) -> Result<Box<str>, StreamError> {
+        let key = self.parse().await?;
+        self.expect(b':').await?;
+        Ok(key)
+    }
+
+    /// Consumes the next complete JSON value and returns its position in the buffer.
+    async fn value(&mut self) -> Result<Range<usize>, StreamError> {
+        self.peek().await?;
+        loop {
+            // `fill` may compact the buffer, so the start is re-read on every pass.
+            let start = self.pos;
+            if let Some(len) = value_len(&self.buf[start..], self.eof) {
+                self.pos += len;
+                return Ok(start..self.pos);
+            }
+            if self.eof {
+                return Err(syntax_error("unexpected end of JSON results"));
+            }
+            self.fill().await?;
+        }
+    }
+
+    async fn parse<T: DeserializeOwned>(&mut self) -> Result<T, StreamError> {
+        let range = self.value().await?;
+        Ok(serde_json::from_slice(&self.buf[range]).map_err(ParseError::from)?)
+    }
+}
+
+/// Returns the length of the JSON value at the start of `bytes`, or `None` if
+/// more input is needed to find its end.
+///
+/// Only strings and nesting are tracked; the value itself is validated later
+/// by `serde_json`.
+fn value_len(bytes: &[u8], eof: bool) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, &b) in bytes.iter().enumerate() {
+        if in_string {
+            match b {
+                _ if escaped => escaped = false,
+                b'\\' => escaped = true,
+                b'"' => {
+                    in_string = false;
+                    if depth == 0 {
+                        return Some(i + 1);
+                    }
+                }
+                _ => {}
+            }
+            continue;
+        }
+        match b {
+            b'"' => in_string = true,
+            b'{' | b'[' => depth += 1,
+            b'}' | b']' if depth == 0 => return Some(i),
+            b'}' | b']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i + 1);
+                }
+            }
+            b',' | b' ' | b'\t' | b'\n' | b'\r' if depth == 0 => return Some(i),
+            _ => {}
+        }
+    }
+    (eof && depth == 0 && !in_string && !bytes.is_empty()).then_some(bytes.len())
+}
+
+fn syntax_error(msg: &str) -> StreamError {
+    ParseError::from(<serde_json::Error as serde::de::Error>::custom(msg)).into()
+}
+
+fn into_row(binding: JsonBinding, vars: &[Arc<str>]) -> Result<Row, ParseError> {
+    binding
+        .into_iter()
+        .map(|(name, term)| {
+            let var = vars
+                .iter()
+                .find(|v| ***v == *name)
+                .cloned()
+                .unwrap_or_else(|| name.into());
+            Ok((var, term.into_term()?))
+        })
+        .collect()
+}
+
+/// Reads the `head` of a JSON SELECT response and returns the remaining rows as a stream.
+///
+/// Bindings that precede `head` in the document are buffered until it is found.
+pub(super) async fn select<S, B>(
+    stream: S,
+) -> Result<(Arc<[Arc<str>]>, Option<Box<[Box<str>]>>, RowStream), StreamError>
+where
+    S: Stream<Item = Result<B, reqwest::Error>> + Unpin + Send + 'static,
+    B: AsRef<[u8]> + Send,
+{
+    let mut events = JsonEvents::new(stream);
+    let mut pending = VecDeque::new();
+    let head = loop {
+        match events.next_event().await? {
+            Some(Event::Head(head)) => break head,
+            Some(Event::Binding(binding)) => pending.push_back(binding),
+            None => break JsonHead::default(),
+        }
+    };
+
+    let vars: Arc<[Arc<str>]> = head.vars.iter().map(|v| Arc::from(&**v)).collect();
+
+    let vars_cloned = Arc::clone(&vars);
+    let rows = Box::pin(stream::try_unfold(
+        (events, pending),
+        move |(mut events, mut pending)| {
+            let vars = Arc::clone(&vars_cloned);
+            async move {
+                let binding = match pending.pop_front() {
+                    Some(binding) => binding,
+                    None => loop {
+                        match events.next_event().await? {
+                            Some(Event::Binding(binding)) => break binding,
+                            Some(Event::Head(_)) => continue,
+                            None => return Ok(None),
+                        }
+                    },
+                };
+                let row = into_row(binding, &vars)?;
+                Ok(Some((row, (events, pending))))
+            }
+        },
+    ));
+
+    Ok((vars, head.link, rows))
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::TryStreamExt;
+
+    use super::*;
+    use crate::response::BaseDirection;
+
+    /// Feeds `doc` to the parser one byte at a time to exercise every buffer boundary.
+    async fn parse(doc: &'static str) -> anyhow::Result<(Arc<[Arc<str>]>, Vec<Row>)> {
+        let chunks = stream::iter(doc.as_bytes().chunks(1).map(Ok::<_, reqwest::Error>));
+        let (vars, _, rows) = select(chunks).await?;
+        Ok((vars, rows.try_collect().await?))
+    }
+
+    #[tokio::test]
+    async fn rows_and_terms() -> anyhow::Result<()> {
+        let (vars, rows) = parse(
+            r#"{
+                "head": { "vars": ["s", "label", "n"] },
+                "results": { "bindings": [
+                    {
+                        "s": { "type": "uri", "value": "http://example.org/a" },
+                        "label": { "type": "literal", "value": "a \"quoted\" {label}", "xml:lang": "EN" },
+                        "title": { "type": "literal", "value": "عنوان", "xml:lang": "ar", "its:dir": "rtl" },
+                        "n": { "type": "literal", "value": "1", "datatype": "http://www.w3.org/2001/XMLSchema#integer" }
+                    },
+                    { "s": { "type": "bnode", "value": "b0" } }
+                ] }
+            }"#,
+        )
+        .await?;
+
+        assert_eq!(&*vars, [Arc::from("s"), "label".into(), "n".into()]);
+        assert_eq!(rows.len(), 2);
+        assert!(rows[0]["s"].is_iri());
+        assert_eq!(&*rows[0]["label"].value, r#"a "quoted" {label}"#);
+        assert_eq!(rows[0]["label"].lang(), Some("en"));
+        assert_eq!(
+            rows[0]["title"],
+            RDFTerm::dir_lang_literal("عنوان", "ar", BaseDirection::Rtl)
+        );
+        assert_eq!(
+            rows[0]["n"].datatype(),
+            Some("http://www.w3.org/2001/XMLSchema#integer")
+        );
+        assert!(rows[1]["s"].is_blank_node());
+        assert!(!rows[1].contains_key("label"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn triple_terms() -> anyhow::Result<()> {
+        let (_, rows) = parse(
+            r#"{
+                "head": { "vars": ["t"] },
+                "results": { "bindings": [ { "t": { "type": "triple", "value": {
+                    "subject": { "type": "uri", "value": "http://example.org/s" },
+                    "predicate": { "type": "uri", "value": "http://example.org/p" },
+                    "object": { "type": "triple", "value": {
+                        "subject": { "type": "bnode", "value": "b0" },
+                        "predicate": { "type": "uri", "value": "http://example.org/q" },
+                        "object": { "type": "literal", "value": "o" }
+                    } }
+                } } } ] }
+            }"#,
+        )
+        .await?;
+
+        let triple = rows[0]["t"].as_triple().unwrap();
+        assert_eq!(triple.subject, RDFTerm::iri("http://example.org/s"));
+        let inner = triple.object.as_triple().unwrap();
+        assert!(inner.subject.is_blank_node());
+        assert_eq!(inner.object, RDFTerm::literal("o"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn head_after_results() -> anyhow::Result<()> {
+        let (vars, rows) = parse(
+            r#"{
+                "results": { "distinct": false, "bindings": [
+                    { "x": { "type": "typed-literal", "value": "true", "datatype": "http://www.w3.org/2001/XMLSchema#boolean" } }
+                ] },
+                "head": { "vars": ["x"], "link": ["http://example.org/meta"] }
+            }"#,
+        )
+        .await?;
+
+        assert_eq!(&*vars, [Arc::from("x")]);
+        assert_eq!(&*rows[0]["x"].value, "true");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn link() -> anyhow::Result<()> {
+        let doc =
+            r#"{"head":{"vars":[],"link":["http://example.org/meta"]},"results":{"bindings":[]}}"#;
+        let chunks = stream::iter([Ok::<_, reqwest::Error>(doc.as_bytes())]);
+        let (_, link, _) = select(chunks).await?;
+
+        assert_eq!(
+            link.as_deref(),
+            Some(&[Box::from("http://example.org/meta")][..])
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn truncated() {
+        let err = parse(r#"{"head":{"vars":["x"]},"results":{"bindings":[{"x":"#)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("end of JSON"), "{err}");
+    }
+}
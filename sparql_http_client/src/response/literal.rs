@@ -0,0 +1,1006 @@
+use std::num::IntErrorKind;
+
+use thiserror::Error;
+
+use super::term::{BaseDirection, LiteralType, RDFTerm, RDFType};
+
+const XSD: &str = "http://www.w3.org/2001/XMLSchema#";
+const XSD_STRING: &str = "http://www.w3.org/2001/XMLSchema#string";
+const RDF_LANG_STRING: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#langString";
+const RDF_DIR_LANG_STRING: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#dirLangString";
+
+/// Error returned when converting an [`RDFTerm`] into a typed value fails.
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum LiteralError {
+    #[error("expected a literal, found {0}")]
+    NotALiteral(Box<str>),
+    #[error("expected an {expected} literal, found datatype <{found}>")]
+    UnexpectedDatatype {
+        expected: &'static str,
+        found: Box<str>,
+    },
+    #[error("invalid {datatype} lexical form: {value:?}")]
+    InvalidLexicalForm {
+        datatype: &'static str,
+        value: Box<str>,
+    },
+    #[error("{value:?} is out of range for {target}")]
+    OutOfRange {
+        value: Box<str>,
+        target: &'static str,
+    },
+    #[error("{0:?} has no timezone")]
+    MissingTimezone(Box<str>),
+    #[error("duration {0:?} has years or months, which have no fixed length")]
+    IndefiniteDuration(Box<str>),
+}
+
+/// The value of a literal, parsed according to its XSD datatype.
+///
+/// Returned by [`RDFTerm::to_typed`]. Lexical forms follow the XSD rules:
+/// surrounding whitespace is ignored, numbers may have a leading `+`, doubles
+/// may be `INF`, `-INF` or `NaN`, and dates and times may carry a timezone
+/// offset.
+///
+/// ```
+/// use sparql_http_client::response::{RDFTerm, TypedLiteral};
+///
+/// let term: RDFTerm = r#""+42"^^<http://www.w3.org/2001/XMLSchema#int>"#.parse()?;
+/// assert_eq!(term.to_typed()?, TypedLiteral::Integer(42));
+///
+/// let term: RDFTerm = r#""-INF"^^<http://www.w3.org/2001/XMLSchema#double>"#.parse()?;
+/// assert_eq!(term.to_typed()?, TypedLiteral::Double(f64::NEG_INFINITY));
+///
+/// let term: RDFTerm = r#""2024-02-29T12:00:00+01:00"^^<http://www.w3.org/2001/XMLSchema#dateTime>"#.parse()?;
+/// let TypedLiteral::DateTime(date_time) = term.to_typed()? else { unreachable!() };
+/// assert_eq!((date_time.month, date_time.day, date_time.offset), (2, 29, Some(60)));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypedLiteral<'a> {
+    /// A plain literal or an `xsd:string`.
+    String(&'a str),
+    /// A language-tagged string, with its base direction if it has one.
+    LangString {
+        value: &'a str,
+        lang: &'a str,
+        direction: Option<BaseDirection>,
+    },
+    /// An `xsd:boolean`.
+    Boolean(bool),
+    /// An `xsd:integer` or one of its derived types, such as `xsd:int` or
+    /// `xsd:nonNegativeInteger`.
+    Integer(i64),
+    /// An `xsd:decimal`, as its validated lexical form, which has no
+    /// fixed-precision equivalent in the standard library.
+    Decimal(&'a str),
+    /// An `xsd:double` or `xsd:float`.
+    Double(f64),
+    /// An `xsd:dateTime` or `xsd:dateTimeStamp`.
+    DateTime(XsdDateTime),
+    /// An `xsd:date`.
+    Date(XsdDate),
+    /// An `xsd:duration`, `xsd:dayTimeDuration` or `xsd:yearMonthDuration`.
+    Duration(XsdDuration),
+    /// A literal of any other datatype.
+    Other { value: &'a str, datatype: &'a str },
+}
+
+/// An `xsd:dateTime` value, in the proleptic Gregorian calendar.
+///
+/// A time of `24:00:00` is read as midnight at the start of the next day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct XsdDateTime {
+    pub year: i64,
+    pub month: u8,
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+    /// Fractional seconds, truncated to nanoseconds.
+    pub nanosecond: u32,
+    /// The timezone offset from UTC in minutes, if any.
+    pub offset: Option<i16>,
+}
+
+/// An `xsd:date` value, in the proleptic Gregorian calendar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct XsdDate {
+    pub year: i64,
+    pub month: u8,
+    pub day: u8,
+    /// The timezone offset from UTC in minutes, if any.
+    pub offset: Option<i16>,
+}
+
+/// An `xsd:duration` value: a number of months and a number of seconds, which
+/// XSD keeps apart since months have no fixed length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct XsdDuration {
+    pub negative: bool,
+    /// Years and months, in months.
+    pub months: u64,
+    /// Days, hours, minutes and whole seconds, in seconds.
+    pub seconds: u64,
+    /// Fractional seconds, truncated to nanoseconds.
+    pub nanoseconds: u32,
+}
+
+impl RDFTerm {
+    /// Parses a literal according to its XSD datatype.
+    ///
+    /// Fails if the term is not a literal or its lexical form is not valid for
+    /// its datatype. Literals of datatypes without a dedicated variant are
+    /// returned as [`TypedLiteral::Other`].
+    pub fn to_typed(&self) -> Result<TypedLiteral<'_>, LiteralError> {
+        let datatype = match &self.kind {
+            RDFType::Literal(LiteralType::Plain) => return Ok(TypedLiteral::String(&self.value)),
+            RDFType::Literal(LiteralType::Lang(lang)) => {
+                return Ok(TypedLiteral::LangString {
+                    value: &self.value,
+                    lang,
+                    direction: None,
+                })
+            }
+            RDFType::Literal(LiteralType::DirLang(lang, direction)) => {
+                return Ok(TypedLiteral::LangString {
+                    value: &self.value,
+                    lang,
+                    direction: Some(*direction),
+                })
+            }
+            RDFType::Literal(LiteralType::Datatype(datatype)) => datatype,
+            _ => return Err(LiteralError::NotALiteral(self.to_string().into())),
+        };
+        let value = collapse(&self.value);
+        Ok(match datatype.strip_prefix(XSD).unwrap_or_default() {
+            "string" => TypedLiteral::String(&self.value),
+            "boolean" => TypedLiteral::Boolean(parse_boolean(value)?),
+            "decimal" => TypedLiteral::Decimal(parse_decimal(value)?),
+            "double" | "float" => TypedLiteral::Double(parse_double(value)?),
+            "dateTime" | "dateTimeStamp" => TypedLiteral::DateTime(parse_date_time(value)?),
+            "date" => TypedLiteral::Date(parse_date(value)?),
+            "duration" | "dayTimeDuration" | "yearMonthDuration" => {
+                TypedLiteral::Duration(parse_duration(value)?)
+            }
+            name if is_integer_type(name) => TypedLiteral::Integer(parse_integer_of(name, value)?),
+            _ => TypedLiteral::Other {
+                value: &self.value,
+                datatype,
+            },
+        })
+    }
+}
+
+/// `xsd:integer` and its derived types; accepts every integer datatype within
+/// the range of that datatype.
+impl TryFrom<&RDFTerm> for i64 {
+    type Error = LiteralError;
+
+    fn try_from(term: &RDFTerm) -> Result<Self, Self::Error> {
+        let (name, value) = lexical(term, "xsd:integer", is_integer_type)?;
+        parse_integer_of(name, value)
+    }
+}
+
+/// `xsd:double` or `xsd:float`, and `xsd:decimal` or integer types promoted
+/// to a double as in SPARQL arithmetic.
+impl TryFrom<&RDFTerm> for f64 {
+    type Error = LiteralError;
+
+    fn try_from(term: &RDFTerm) -> Result<Self, Self::Error> {
+        let (name, value) = lexical(term, "xsd:double", |name| {
+            matches!(name, "double" | "float" | "decimal") || is_integer_type(name)
+        })?;
+        match name {
+            "double" | "float" => parse_double(value),
+            "decimal" => Ok(parse_decimal(value)?.parse().expect("valid decimal")),
+            name => match parse_integer_of(name, value) {
+                Ok(integer) => Ok(integer as f64),
+                Err(LiteralError::OutOfRange { target: "i64", .. }) => {
+                    Ok(value.parse().expect("valid integer"))
+                }
+                Err(e) => Err(e),
+            },
+        }
+    }
+}
+
+/// `xsd:boolean`.
+impl TryFrom<&RDFTerm> for bool {
+    type Error = LiteralError;
+
+    fn try_from(term: &RDFTerm) -> Result<Self, Self::Error> {
+        let (_, value) = lexical(term, "xsd:boolean", |name| name == "boolean")?;
+        parse_boolean(value)
+    }
+}
+
+/// `xsd:decimal` or an integer type.
+#[cfg(feature = "decimal")]
+impl TryFrom<&RDFTerm> for rust_decimal::Decimal {
+    type Error = LiteralError;
+
+    fn try_from(term: &RDFTerm) -> Result<Self, Self::Error> {
+        let (name, value) = lexical(term, "xsd:decimal", |name| {
+            name == "decimal" || is_integer_type(name)
+        })?;
+        let decimal = if name == "decimal" {
+            parse_decimal(value)?
+        } else {
+            match parse_integer_of(name, value) {
+                Ok(_) | Err(LiteralError::OutOfRange { target: "i64", .. }) => value,
+                Err(e) => return Err(e),
+            }
+        };
+        // `rust_decimal` wants digits on both sides of the point, if any.
+        let (sign, digits) = match decimal.as_bytes()[0] {
+            b'-' => ("-", &decimal[1..]),
+            b'+' => ("", &decimal[1..]),
+            _ => ("", decimal),
+        };
+        let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+        let integer = if integer.is_empty() { "0" } else { integer };
+        let fraction = if fraction.is_empty() { "0" } else { fraction };
+        format!("{sign}{integer}.{fraction}")
+            .parse()
+            .map_err(|_| out_of_range(value, "rust_decimal::Decimal"))
+    }
+}
+
+/// `xsd:date`, ignoring its timezone.
+#[cfg(feature = "chrono")]
+impl TryFrom<&RDFTerm> for chrono::NaiveDate {
+    type Error = LiteralError;
+
+    fn try_from(term: &RDFTerm) -> Result<Self, Self::Error> {
+        let (_, value) = lexical(term, "xsd:date", |name| name == "date")?;
+        let date = parse_date(value)?;
+        chrono_date(date.year, date.month, date.day)
+            .ok_or_else(|| out_of_range(value, "chrono::NaiveDate"))
+    }
+}
+
+/// `xsd:dateTime`, as written, ignoring its timezone.
+#[cfg(feature = "chrono")]
+impl TryFrom<&RDFTerm> for chrono::NaiveDateTime {
+    type Error = LiteralError;
+
+    fn try_from(term: &RDFTerm) -> Result<Self, Self::Error> {
+        let (_, value) = lexical(term, "xsd:dateTime", is_date_time_type)?;
+        chrono_date_time(&parse_date_time(value)?)
+            .ok_or_else(|| out_of_range(value, "chrono::NaiveDateTime"))
+    }
+}
+
+/// `xsd:dateTime` with a timezone.
+#[cfg(feature = "chrono")]
+impl TryFrom<&RDFTerm> for chrono::DateTime<chrono::FixedOffset> {
+    type Error = LiteralError;
+
+    fn try_from(term: &RDFTerm) -> Result<Self, Self::Error> {
+        use chrono::TimeZone;
+
+        let (_, value) = lexical(term, "xsd:dateTime", is_date_time_type)?;
+        let date_time = parse_date_time(value)?;
+        let offset = date_time
+            .offset
+            .ok_or_else(|| LiteralError::MissingTimezone(value.into()))?;
+        let offset = chrono::FixedOffset::east_opt(i32::from(offset) * 60).expect("within ±14:00");
+        chrono_date_time(&date_time)
+            .and_then(|local| offset.from_local_datetime(&local).single())
+            .ok_or_else(|| out_of_range(value, "chrono::DateTime"))
+    }
+}
+
+/// `xsd:dateTime` with a timezone, converted to UTC.
+#[cfg(feature = "chrono")]
+impl TryFrom<&RDFTerm> for chrono::DateTime<chrono::Utc> {
+    type Error = LiteralError;
+
+    fn try_from(term: &RDFTerm) -> Result<Self, Self::Error> {
+        chrono::DateTime::<chrono::FixedOffset>::try_from(term).map(|date_time| date_time.to_utc())
+    }
+}
+
+/// `xsd:duration` without years or months.
+#[cfg(feature = "chrono")]
+impl TryFrom<&RDFTerm> for chrono::TimeDelta {
+    type Error = LiteralError;
+
+    fn try_from(term: &RDFTerm) -> Result<Self, Self::Error> {
+        let (_, value) = lexical(term, "xsd:dayTimeDuration", is_duration_type)?;
+        let duration = parse_duration(value)?;
+        if duration.months != 0 {
+            return Err(LiteralError::IndefiniteDuration(value.into()));
+        }
+        let delta = i64::try_from(duration.seconds)
+            .ok()
+            .and_then(|seconds| chrono::TimeDelta::new(seconds, duration.nanoseconds))
+            .ok_or_else(|| out_of_range(value, "chrono::TimeDelta"))?;
+        Ok(if duration.negative { -delta } else { delta })
+    }
+}
+
+/// `xsd:date`, ignoring its timezone.
+#[cfg(feature = "time")]
+impl TryFrom<&RDFTerm> for time::Date {
+    type Error = LiteralError;
+
+    fn try_from(term: &RDFTerm) -> Result<Self, Self::Error> {
+        let (_, value) = lexical(term, "xsd:date", |name| name == "date")?;
+        let date = parse_date(value)?;
+        time_date(date.year, date.month, date.day).ok_or_else(|| out_of_range(value, "time::Date"))
+    }
+}
+
+/// `xsd:dateTime`, as written, ignoring its timezone.
+#[cfg(feature = "time")]
+impl TryFrom<&RDFTerm> for time::PrimitiveDateTime {
+    type Error = LiteralError;
+
+    fn try_from(term: &RDFTerm) -> Result<Self, Self::Error> {
+        let (_, value) = lexical(term, "xsd:dateTime", is_date_time_type)?;
+        time_date_time(&parse_date_time(value)?)
+            .ok_or_else(|| out_of_range(value, "time::PrimitiveDateTime"))
+    }
+}
+
+/// `xsd:dateTime` with a timezone.
+#[cfg(feature = "time")]
+impl TryFrom<&RDFTerm> for time::OffsetDateTime {
+    type Error = LiteralError;
+
+    fn try_from(term: &RDFTerm) -> Result<Self, Self::Error> {
+        let (_, value) = lexical(term, "xsd:dateTime", is_date_time_type)?;
+        let date_time = parse_date_time(value)?;
+        let offset = date_time
+            .offset
+            .ok_or_else(|| LiteralError::MissingTimezone(value.into()))?;
+        let offset =
+            time::UtcOffset::from_whole_seconds(i32::from(offset) * 60).expect("within ±14:00");
+        time_date_time(&date_time)
+            .map(|local| local.assume_offset(offset))
+            .ok_or_else(|| out_of_range(value, "time::OffsetDateTime"))
+    }
+}
+
+/// `xsd:duration` without years or months.
+#[cfg(feature = "time")]
+impl TryFrom<&RDFTerm> for time::Duration {
+    type Error = LiteralError;
+
+    fn try_from(term: &RDFTerm) -> Result<Self, Self::Error> {
+        let (_, value) = lexical(term, "xsd:dayTimeDuration", is_duration_type)?;
+        let duration = parse_duration(value)?;
+        if duration.months != 0 {
+            return Err(LiteralError::IndefiniteDuration(value.into()));
+        }
+        let seconds =
+            i64::try_from(duration.seconds).map_err(|_| out_of_range(value, "time::Duration"))?;
+        let duration_value = time::Duration::new(seconds, duration.nanoseconds as i32);
+        Ok(if duration.negative {
+            -duration_value
+        } else {
+            duration_value
+        })
+    }
+}
+
+#[cfg(feature = "chrono")]
+fn chrono_date(year: i64, month: u8, day: u8) -> Option<chrono::NaiveDate> {
+    chrono::NaiveDate::from_ymd_opt(year.try_into().ok()?, month.into(), day.into())
+}
+
+#[cfg(feature = "chrono")]
+fn chrono_date_time(value: &XsdDateTime) -> Option<chrono::NaiveDateTime> {
+    chrono_date(value.year, value.month, value.day)?.and_hms_nano_opt(
+        value.hour.into(),
+        value.minute.into(),
+        value.second.into(),
+        value.nanosecond,
+    )
+}
+
+#[cfg(feature = "time")]
+fn time_date(year: i64, month: u8, day: u8) -> Option<time::Date> {
+    let month = time::Month::try_from(month).ok()?;
+    time::Date::from_calendar_date(year.try_into().ok()?, month, day).ok()
+}
+
+#[cfg(feature = "time")]
+fn time_date_time(value: &XsdDateTime) -> Option<time::PrimitiveDateTime> {
+    let date = time_date(value.year, value.month, value.day)?;
+    let time =
+        time::Time::from_hms_nano(value.hour, value.minute, value.second, value.nanosecond).ok()?;
+    Some(time::PrimitiveDateTime::new(date, time))
+}
+
+/// Returns the local name of the XSD datatype of `term` and its lexical form,
+/// with whitespace collapsed, if `accepts` the datatype.
+fn lexical<'a>(
+    term: &'a RDFTerm,
+    expected: &'static str,
+    accepts: impl Fn(&str) -> bool,
+) -> Result<(&'a str, &'a str), LiteralError> {
+    let datatype = match &term.kind {
+        RDFType::Literal(LiteralType::Datatype(datatype)) => datatype,
+        RDFType::Literal(LiteralType::Plain) => XSD_STRING,
+        RDFType::Literal(LiteralType::Lang(_)) => RDF_LANG_STRING,
+        RDFType::Literal(LiteralType::DirLang(..)) => RDF_DIR_LANG_STRING,
+        _ => return Err(LiteralError::NotALiteral(term.to_string().into())),
+    };
+    match datatype.strip_prefix(XSD) {
+        Some(name) if accepts(name) => Ok((name, collapse(&term.value))),
+        _ => Err(LiteralError::UnexpectedDatatype {
+            expected,
+            found: datatype.into(),
+        }),
+    }
+}
+
+/// `xsd:integer` and its derived types, with their prefixed names and the
+/// inclusive bounds of their value spaces; `i128::MIN` and `i128::MAX` stand
+/// for an unbounded side.
+const INTEGER_TYPES: &[(&str, &str, i128, i128)] = &[
+    ("integer", "xsd:integer", i128::MIN, i128::MAX),
+    ("long", "xsd:long", i64::MIN as i128, i64::MAX as i128),
+    ("int", "xsd:int", i32::MIN as i128, i32::MAX as i128),
+    ("short", "xsd:short", i16::MIN as i128, i16::MAX as i128),
+    ("byte", "xsd:byte", i8::MIN as i128, i8::MAX as i128),
+    ("nonNegativeInteger", "xsd:nonNegativeInteger", 0, i128::MAX),
+    ("positiveInteger", "xsd:positiveInteger", 1, i128::MAX),
+    ("nonPositiveInteger", "xsd:nonPositiveInteger", i128::MIN, 0),
+    ("negativeInteger", "xsd:negativeInteger", i128::MIN, -1),
+    ("unsignedLong", "xsd:unsignedLong", 0, u64::MAX as i128),
+    ("unsignedInt", "xsd:unsignedInt", 0, u32::MAX as i128),
+    ("unsignedShort", "xsd:unsignedShort", 0, u16::MAX as i128),
+    ("unsignedByte", "xsd:unsignedByte", 0, u8::MAX as i128),
+];
+
+pub(super) fn is_integer_type(name: &str) -> bool {
+    INTEGER_TYPES.iter().any(|(local, ..)| *local == name)
+}
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+pub(super) fn is_date_time_type(name: &str) -> bool {
+    matches!(name, "dateTime" | "dateTimeStamp")
+}
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+fn is_duration_type(name: &str) -> bool {
+    matches!(name, "duration" | "dayTimeDuration")
+}
+
+/// Strips the whitespace that XSD collapses around numbers, booleans and dates.
+pub(super) fn collapse(value: &str) -> &str {
+    value.trim_matches([' ', '\t', '\n', '\r'])
+}
+
+fn invalid(datatype: &'static str, value: &str) -> LiteralError {
+    LiteralError::InvalidLexicalForm {
+        datatype,
+        value: value.into(),
+    }
+}
+
+fn out_of_range(value: &str, target: &'static str) -> LiteralError {
+    LiteralError::OutOfRange {
+        value: value.into(),
+        target,
+    }
+}
+
+pub(super) fn parse_boolean(value: &str) -> Result<bool, LiteralError> {
+    match value {
+        "true" | "1" => Ok(true),
+        "false" | "0" => Ok(false),
+        _ => Err(invalid("xsd:boolean", value)),
+    }
+}
+
+/// Parses `(+|-)?[0-9]+`.
+fn parse_integer(value: &str) -> Result<i64, LiteralError> {
+    value
+        .parse()
+        .map_err(|e: std::num::ParseIntError| match e.kind() {
+            IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => LiteralError::OutOfRange {
+                value: value.into(),
+                target: "i64",
+            },
+            _ => invalid("xsd:integer", value),
+        })
+}
+
+/// Parses an integer of the integer datatype `name`, which must be in the value
+/// space of that datatype.
+///
+/// A valid integer beyond `i64` that the datatype allows fails with an
+/// [`OutOfRange`](LiteralError::OutOfRange) error whose target is `i64`.
+pub(super) fn parse_integer_of(name: &str, value: &str) -> Result<i64, LiteralError> {
+    let &(_, target, min, max) = INTEGER_TYPES
+        .iter()
+        .find(|(local, ..)| *local == name)
+        .expect("an integer datatype");
+    match parse_integer(value) {
+        Ok(integer) if (min..=max).contains(&i128::from(integer)) => Ok(integer),
+        Ok(_) => Err(out_of_range(value, target)),
+        Err(LiteralError::OutOfRange { .. })
+            if (value.starts_with('-') && min >= i128::from(i64::MIN))
+                || (!value.starts_with('-') && max <= i128::from(i64::MAX)) =>
+        {
+            Err(out_of_range(value, target))
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Validates `(+|-)?([0-9]+(.[0-9]*)?|.[0-9]+)`.
+pub(super) fn parse_decimal(value: &str) -> Result<&str, LiteralError> {
+    let digits = value.strip_prefix(['+', '-']).unwrap_or(value);
+    let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    let all_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    if all_digits(integer) && all_digits(fraction) && integer.len() + fraction.len() > 0 {
+        Ok(value)
+    } else {
+        Err(invalid("xsd:decimal", value))
+    }
+}
+
+/// Parses a decimal with an optional exponent, `INF`, `+INF`, `-INF` or `NaN`.
+pub(super) fn parse_double(value: &str) -> Result<f64, LiteralError> {
+    match value {
+        "INF" | "+INF" => return Ok(f64::INFINITY),
+        "-INF" => return Ok(f64::NEG_INFINITY),
+        "NaN" => return Ok(f64::NAN),
+        _ => {}
+    }
+    let (mantissa, exponent) = value.split_once(['e', 'E']).unwrap_or((value, "0"));
+    let exponent = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+    if parse_decimal(mantissa).is_err()
+        || exponent.is_empty()
+        || !exponent.bytes().all(|b| b.is_ascii_digit())
+    {
+        return Err(invalid("xsd:double", value));
+    }
+    Ok(value.parse().expect("valid double"))
+}
+
+pub(super) fn parse_date_time(value: &str) -> Result<XsdDateTime, LiteralError> {
+    let parsed = (|| {
+        let (year, month, day, rest) = date_part(value)?;
+        let (hour, minute, second, nanosecond, rest) = time_part(rest.strip_prefix('T')?)?;
+        let offset = timezone(rest)?;
+        let mut date_time = XsdDateTime {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+            nanosecond,
+            offset,
+        };
+        if hour == 24 {
+            date_time.hour = 0;
+            date_time.day += 1;
+            if date_time.day > days_in_month(year, month) {
+                date_time.day = 1;
+                date_time.month += 1;
+            }
+            if date_time.month > 12 {
+                date_time.month = 1;
+                date_time.year = year.checked_add(1)?;
+            }
+        }
+        Some(date_time)
+    })();
+    parsed.ok_or_else(|| invalid("xsd:dateTime", value))
+}
+
+pub(super) fn parse_date(value: &str) -> Result<XsdDate, LiteralError> {
+    let parsed = (|| {
+        let (year, month, day, rest) = date_part(value)?;
+        Some(XsdDate {
+            year,
+            month,
+            day,
+            offset: timezone(rest)?,
+        })
+    })();
+    parsed.ok_or_else(|| invalid("xsd:date", value))
+}
+
+/// Parses `-?P(nY)?(nM)?(nD)?(T(nH)?(nM)?(n(.n)?S)?)?` with at least one
+/// component.
+fn parse_duration(value: &str) -> Result<XsdDuration, LiteralError> {
+    let parsed = (|| {
+        let (negative, rest) = match value.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, value),
+        };
+        let rest = rest.strip_prefix('P')?;
+        let (date, time) = match rest.split_once('T') {
+            Some((_, "")) => return None,
+            Some((date, time)) => (date, Some(time)),
+            None => (rest, None),
+        };
+
+        let mut duration = XsdDuration {
+            negative,
+            months: 0,
+            seconds: 0,
+            nanoseconds: 0,
+        };
+        let mut components = 0;
+        for (number, designator) in components_of(date, &['Y', 'M', 'D'])? {
+            let number: u64 = number.parse().ok()?;
+            match designator {
+                'Y' => duration.months = number.checked_mul(12)?.checked_add(duration.months)?,
+                'M' => duration.months = duration.months.checked_add(number)?,
+                _ => duration.seconds = number.checked_mul(86_400)?,
+            }
+            components += 1;
+        }
+        for (number, designator) in components_of(time.unwrap_or_default(), &['H', 'M', 'S'])? {
+            let (whole, fraction) = match number.split_once('.') {
+                Some((whole, fraction))
+                    if designator == 'S'
+                        && !fraction.is_empty()
+                        && fraction.bytes().all(|b| b.is_ascii_digit()) =>
+                {
+                    (whole, fraction)
+                }
+                Some(_) => return None,
+                None => (number, ""),
+            };
+            let scale = match designator {
+                'H' => 3600,
+                'M' => 60,
+                _ => 1,
+            };
+            let seconds = whole.parse::<u64>().ok()?.checked_mul(scale)?;
+            duration.seconds = duration.seconds.checked_add(seconds)?;
+            duration.nanoseconds = nanoseconds(fraction);
+            components += 1;
+        }
+        (components > 0).then_some(duration)
+    })();
+    parsed.ok_or_else(|| invalid("xsd:duration", value))
+}
+
+/// Splits `value` into numbers each followed by one of `designators`, which
+/// must appear in order.
+fn components_of<'a>(mut value: &'a str, designators: &[char]) -> Option<Vec<(&'a str, char)>> {
+    let mut components = Vec::new();
+    let mut allowed = designators;
+    while !value.is_empty() {
+        let end = value.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+        let designator = value[end..].chars().next()?;
+        let position = allowed.iter().position(|d| *d == designator)?;
+        if end == 0 || value.starts_with('.') {
+            return None;
+        }
+        components.push((&value[..end], designator));
+        allowed = &allowed[position + 1..];
+        value = &value[end + 1..];
+    }
+    Some(components)
+}
+
+/// Parses `-?YYYY-MM-DD`, where the year has at least four digits and no
+/// leading zero beyond four.
+fn date_part(value: &str) -> Option<(i64, u8, u8, &str)> {
+    let (negative, rest) = match value.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, value),
+    };
+    let year_len = rest.find(|c: char| !c.is_ascii_digit())?;
+    if year_len < 4 || (year_len > 4 && rest.starts_with('0')) {
+        return None;
+    }
+    let year: i64 = rest[..year_len].parse().ok()?;
+    let year = if negative { -year } else { year };
+    let rest = rest[year_len..].strip_prefix('-')?;
+    let (month, rest) = two_digits(rest)?;
+    let (day, rest) = two_digits(rest.strip_prefix('-')?)?;
+    if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+        return None;
+    }
+    Some((year, month, day, rest))
+}
+
+/// Parses `hh:mm:ss(.s+)?`, allowing `24:00:00`.
+fn time_part(value: &str) -> Option<(u8, u8, u8, u32, &str)> {
+    let (hour, rest) = two_digits(value)?;
+    let (minute, rest) = two_digits(rest.strip_prefix(':')?)?;
+    let (second, mut rest) = two_digits(rest.strip_prefix(':')?)?;
+    let mut nanosecond = 0;
+    if let Some(fraction) = rest.strip_prefix('.') {
+        let len = fraction
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(fraction.len());
+        if len == 0 {
+            return None;
+        }
+        nanosecond = nanoseconds(&fraction[..len]);
+        rest = &fraction[len..];
+    }
+    let midnight = hour == 24 && minute == 0 && second == 0 && nanosecond == 0;
+    if (hour > 23 && !midnight) || minute > 59 || second > 59 {
+        return None;
+    }
+    Some((hour, minute, second, nanosecond, rest))
+}
+
+/// Parses an empty timezone, `Z`, or `(+|-)hh:mm` up to `14:00`, into an
+/// offset in minutes.
+fn timezone(value: &str) -> Option<Option<i16>> {
+    let (sign, rest) = match value.as_bytes().first() {
+        None => return Some(None),
+        Some(b'Z') if value.len() == 1 => return Some(Some(0)),
+        Some(b'+') => (1, &value[1..]),
+        Some(b'-') => (-1, &value[1..]),
+        _ => return None,
+    };
+    let (hours, rest) = two_digits(rest)?;
+    let (minutes, rest) = two_digits(rest.strip_prefix(':')?)?;
+    if !rest.is_empty() || minutes > 59 || hours > 14 || (hours == 14 && minutes > 0) {
+        return None;
+    }
+    Some(Some(sign * (i16::from(hours) * 60 + i16::from(minutes))))
+}
+
+fn two_digits(value: &str) -> Option<(u8, &str)> {
+    match value.as_bytes() {
+        [a @ b'0'..=b'9', b @ b'0'..=b'9', ..] => Some(((a - b'0') * 10 + (b - b'0'), &value[2..])),
+        _ => None,
+    }
+}
+
+/// Converts the digits after a decimal point into nanoseconds, truncating
+/// beyond nine digits.
+fn nanoseconds(fraction: &str) -> u32 {
+    fraction
+        .bytes()
+        .chain(std::iter::repeat(b'0'))
+        .take(9)
+        .fold(0, |n, digit| n * 10 + u32::from(digit - b'0'))
+}
+
+fn days_in_month(year: i64, month: u8) -> u8 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn typed(value: &str, datatype: &str) -> RDFTerm {
+        RDFTerm::typed_literal(value, format!("{XSD}{datatype}"))
+    }
+
+    #[test]
+    fn integers() {
+        assert_eq!(i64::try_from(&typed(" +42\n", "integer")), Ok(42));
+        assert_eq!(i64::try_from(&typed("255", "unsignedByte")), Ok(255));
+        assert_eq!(i64::try_from(&typed("-0", "nonNegativeInteger")), Ok(0));
+        for (value, datatype) in [
+            ("-7", "unsignedByte"),
+            ("-1", "nonNegativeInteger"),
+            ("1000", "byte"),
+            ("0", "positiveInteger"),
+            ("0", "negativeInteger"),
+            ("99999999999999999999", "long"),
+            ("-99999999999999999999", "unsignedLong"),
+        ] {
+            let term = typed(value, datatype);
+            let expected = LiteralError::OutOfRange {
+                value: value.into(),
+                target: INTEGER_TYPES.iter().find(|t| t.0 == datatype).unwrap().1,
+            };
+            assert_eq!(i64::try_from(&term), Err(expected.clone()), "{datatype}");
+            assert_eq!(term.to_typed(), Err(expected), "{datatype}");
+        }
+        assert!(matches!(
+            i64::try_from(&typed("99999999999999999999", "integer")),
+            Err(LiteralError::OutOfRange { .. })
+        ));
+        assert!(matches!(
+            i64::try_from(&typed("4.0", "integer")),
+            Err(LiteralError::InvalidLexicalForm { .. })
+        ));
+        assert_eq!(
+            i64::try_from(&RDFTerm::literal("42")),
+            Err(LiteralError::UnexpectedDatatype {
+                expected: "xsd:integer",
+                found: XSD_STRING.into()
+            })
+        );
+        assert!(matches!(
+            i64::try_from(&RDFTerm::iri("http://example.org/")),
+            Err(LiteralError::NotALiteral(_))
+        ));
+    }
+
+    #[test]
+    fn doubles_and_booleans() {
+        assert_eq!(f64::try_from(&typed("+INF", "double")), Ok(f64::INFINITY));
+        assert_eq!(f64::try_from(&typed("-1.5E-3", "float")), Ok(-0.0015));
+        assert_eq!(f64::try_from(&typed(".5", "decimal")), Ok(0.5));
+        assert_eq!(f64::try_from(&typed("3", "int")), Ok(3.0));
+        assert!(f64::try_from(&typed("NaN", "double")).unwrap().is_nan());
+        for invalid in ["inf", "1e", "1.5f", "", "."] {
+            assert!(
+                f64::try_from(&typed(invalid, "double")).is_err(),
+                "{invalid}"
+            );
+        }
+        assert_eq!(bool::try_from(&typed("1", "boolean")), Ok(true));
+        assert_eq!(bool::try_from(&typed(" false ", "boolean")), Ok(false));
+        assert!(bool::try_from(&typed("True", "boolean")).is_err());
+    }
+
+    #[test]
+    fn dates_and_times() {
+        let term = typed("2023-12-31T24:00:00-05:30", "dateTime");
+        assert_eq!(
+            term.to_typed(),
+            Ok(TypedLiteral::DateTime(XsdDateTime {
+                year: 2024,
+                month: 1,
+                day: 1,
+                hour: 0,
+                minute: 0,
+                second: 0,
+                nanosecond: 0,
+                offset: Some(-330),
+            }))
+        );
+        let TypedLiteral::DateTime(date_time) =
+            typed("-12345-06-07T08:09:10.1234567891Z", "dateTime")
+                .to_typed()
+                .unwrap()
+        else {
+            panic!()
+        };
+        assert_eq!(
+            (date_time.year, date_time.nanosecond),
+            (-12345, 123_456_789)
+        );
+        assert_eq!(
+            typed("2024-02-29", "date").to_typed(),
+            Ok(TypedLiteral::Date(XsdDate {
+                year: 2024,
+                month: 2,
+                day: 29,
+                offset: None
+            }))
+        );
+        for invalid in [
+            "2023-02-29",
+            "02024-01-01",
+            "2024-01-01T24:00:01",
+            "2024-01-01+15:00",
+            "24-01-01",
+        ] {
+            assert!(typed(invalid, "date").to_typed().is_err(), "{invalid}");
+            let date_time = typed(&format!("{invalid}T00:00:00"), "dateTime");
+            assert!(date_time.to_typed().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn durations() {
+        assert_eq!(
+            typed("-P1Y2M3DT4H5M6.5S", "duration").to_typed(),
+            Ok(TypedLiteral::Duration(XsdDuration {
+                negative: true,
+                months: 14,
+                seconds: 3 * 86_400 + 4 * 3600 + 5 * 60 + 6,
+                nanoseconds: 500_000_000,
+            }))
+        );
+        for invalid in [
+            "P", "PT", "P1D2Y", "PT1.5M", "P1H", "1D", "P-1D", "PT1.5.5S", "PT1.xS",
+        ] {
+            assert!(typed(invalid, "duration").to_typed().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn untyped_literals() {
+        assert_eq!(
+            RDFTerm::literal("a").to_typed(),
+            Ok(TypedLiteral::String("a"))
+        );
+        assert_eq!(
+            RDFTerm::lang_literal("chat", "fr").to_typed(),
+            Ok(TypedLiteral::LangString {
+                value: "chat",
+                lang: "fr",
+                direction: None
+            })
+        );
+        assert_eq!(
+            RDFTerm::typed_literal(
+                "POINT(1 2)",
+                "http://www.opengis.net/ont/geosparql#wktLiteral"
+            )
+            .to_typed(),
+            Ok(TypedLiteral::Other {
+                value: "POINT(1 2)",
+                datatype: "http://www.opengis.net/ont/geosparql#wktLiteral"
+            })
+        );
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn decimals() {
+        use rust_decimal::Decimal;
+
+        assert_eq!(
+            Decimal::try_from(&typed("+.50", "decimal")),
+            Ok(Decimal::new(50, 2))
+        );
+        assert_eq!(
+            Decimal::try_from(&typed("-3.", "decimal")),
+            Ok(Decimal::new(-3, 0))
+        );
+        assert_eq!(
+            Decimal::try_from(&typed("12", "integer")),
+            Ok(Decimal::new(12, 0))
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_values() {
+        use chrono::{DateTime, NaiveDate, TimeDelta, Utc};
+
+        let term = typed("2024-03-01T01:30:00+02:00", "dateTime");
+        let utc = DateTime::<Utc>::try_from(&term).unwrap();
+        assert_eq!(utc.to_rfc3339(), "2024-02-29T23:30:00+00:00");
+        let local = typed("2024-03-01T01:30:00", "dateTime");
+        assert!(matches!(
+            DateTime::<Utc>::try_from(&local),
+            Err(LiteralError::MissingTimezone(_))
+        ));
+        assert_eq!(
+            NaiveDate::try_from(&typed("2024-03-01Z", "date")),
+            Ok(NaiveDate::from_ymd_opt(2024, 3, 1).unwrap())
+        );
+        assert_eq!(
+            TimeDelta::try_from(&typed("-PT1M30.5S", "dayTimeDuration")),
+            Ok(-TimeDelta::milliseconds(90_500))
+        );
+        assert!(matches!(
+            TimeDelta::try_from(&typed("P1M", "duration")),
+            Err(LiteralError::IndefiniteDuration(_))
+        ));
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn time_values() {
+        use time::{Duration, Month, OffsetDateTime};
+
+        let term = typed("2024-03-01T01:30:00-01:00", "dateTime");
+        let date_time = OffsetDateTime::try_from(&term).unwrap();
+        assert_eq!(date_time.unix_timestamp(), 1_709_260_200);
+        assert_eq!(
+            time::Date::try_from(&typed("2024-03-01", "date")),
+            Ok(time::Date::from_calendar_date(2024, Month::March, 1).unwrap())
+        );
+        assert_eq!(
+            Duration::try_from(&typed("P1DT1S", "duration")),
+            Ok(Duration::seconds(86_401))
+        );
+    }
+}
@@ -0,0 +1,213 @@
+use std::fmt::Display;
+
+use serde::de::{self, Deserialize};
+
+use super::de::{RowError, TermDeserializer};
+use super::select::Row;
+use super::term::RDFTerm;
+
+/// Conversion from a SELECT result [`Row`] into a user type.
+///
+/// Usually derived with `#[derive(FromRow)]`, which maps every named field onto
+/// the variable of the same name. A field attribute `#[sparql(var = "...")]`
+/// picks another variable, and `#[sparql(with = path)]` converts the term with
+/// a function `fn(&RDFTerm) -> Result<T, E>` where `E: Display`, instead of the
+/// default [`from_term`] conversion. Fields of type `Option<T>` accept unbound
+/// variables; all other fields are required.
+///
+/// ```
+/// use sparql_http_client::FromRow;
+/// use sparql_http_client::response::{RDFTerm, Row};
+///
+/// #[derive(FromRow)]
+/// struct Country {
+///     #[sparql(var = "countryLabel")]
+///     label: String,
+///     #[sparql(with = wikidata_id)]
+///     country: String,
+///     population: Option<u64>,
+/// }
+///
+/// fn wikidata_id(term: &RDFTerm) -> Result<String, &'static str> {
+///     let id = term.value.rsplit('/').next().ok_or("not an entity IRI")?;
+///     Ok(id.to_owned())
+/// }
+///
+/// assert_eq!(Country::REQUIRED_VARS, ["countryLabel", "country"]);
+///
+/// let mut row = Row::new();
+/// row.insert("country".into(), "<http://www.wikidata.org/entity/Q142>".parse()?);
+/// row.insert("countryLabel".into(), r#""France"@en"#.parse()?);
+///
+/// let country = Country::from_row(&row)?;
+/// assert_eq!(country.country, "Q142");
+/// assert_eq!(country.population, None);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// With [`query_as!`](crate::query_as!), a SELECT projection that lacks one of
+/// the [`REQUIRED_VARS`](FromRow::REQUIRED_VARS) is a compile error:
+///
+/// ```compile_fail
+/// use sparql_http_client::{Endpoint, FromRow, SparqlClient, query_as};
+///
+/// #[derive(FromRow)]
+/// struct Country {
+///     #[sparql(var = "countryLabel")]
+///     label: String,
+/// }
+///
+/// let endpoint = Endpoint::new(SparqlClient::default(), "https://example.org/sparql");
+/// let query = query_as!(Country, endpoint, "SELECT ?country WHERE { ?country ?p ?o }");
+/// ```
+pub trait FromRow: Sized {
+    /// Every variable read by [`from_row`](FromRow::from_row).
+    const VARS: &'static [&'static str];
+
+    /// The variables that must be bound in every row.
+    const REQUIRED_VARS: &'static [&'static str];
+
+    /// Converts one result row.
+    fn from_row(row: &Row) -> Result<Self, RowError>;
+}
+
+/// Converts a single term into `T` with the same rules as [`from_row`](super::from_row).
+///
+/// This is the default field conversion of `#[derive(FromRow)]`.
+///
+/// ```
+/// use sparql_http_client::response::{from_term, RDFTerm};
+///
+/// let term: RDFTerm = r#""42"^^<http://www.w3.org/2001/XMLSchema#integer>"#.parse()?;
+/// assert_eq!(from_term::<i32>(&term)?, 42);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn from_term<'a, T: Deserialize<'a>>(term: &'a RDFTerm) -> Result<T, RowError> {
+    T::deserialize(TermDeserializer { term })
+}
+
+/// Reads a required variable for `#[derive(FromRow)]`.
+#[doc(hidden)]
+pub fn required<'a, T, E: Display>(
+    row: &'a Row,
+    var: &str,
+    convert: impl FnOnce(&'a RDFTerm) -> Result<T, E>,
+) -> Result<T, RowError> {
+    let term = row
+        .get(var)
+        .ok_or_else(|| de::Error::custom(format_args!("missing variable `{var}`")))?;
+    convert(term).map_err(|e| de::Error::custom(format_args!("variable `{var}`: {e}")))
+}
+
+/// Reads an optional variable for `#[derive(FromRow)]`.
+#[doc(hidden)]
+pub fn optional<'a, T, E: Display>(
+    row: &'a Row,
+    var: &str,
+    convert: impl FnOnce(&'a RDFTerm) -> Result<T, E>,
+) -> Result<Option<T>, RowError> {
+    row.get(var)
+        .map(convert)
+        .transpose()
+        .map_err(|e| de::Error::custom(format_args!("variable `{var}`: {e}")))
+}
+
+/// Fails constant evaluation when a required variable is not in `projected`.
+///
+/// Expanded by `query_as!` into a `const` item, so the failure surfaces as a
+/// compile error naming the missing variable.
+#[doc(hidden)]
+pub const fn assert_projected(required: &[&str], projected: &[&str]) {
+    let mut i = 0;
+    while i < required.len() {
+        let mut found = false;
+        let mut j = 0;
+        while j < projected.len() {
+            found |= const_str_eq(required[i], projected[j]);
+            j += 1;
+        }
+        if !found {
+            // Formatting is not available in `const fn`, so the message is assembled by hand.
+            let mut buf = [0u8; 256];
+            let len = const_concat(
+                &mut buf,
+                &[
+                    "the SELECT projection has no `?",
+                    required[i],
+                    "` variable, which the row type requires",
+                ],
+            );
+            match std::str::from_utf8(buf.split_at(len).0) {
+                Ok(msg) => panic!("{}", msg),
+                Err(_) => panic!("the SELECT projection lacks a variable the row type requires"),
+            }
+        }
+        i += 1;
+    }
+}
+
+const fn const_str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Copies `parts` into `buf`, truncating at its end, and returns the length written.
+const fn const_concat(buf: &mut [u8], parts: &[&str]) -> usize {
+    let mut len = 0;
+    let mut p = 0;
+    while p < parts.len() {
+        let bytes = parts[p].as_bytes();
+        let mut k = 0;
+        while k < bytes.len() && len < buf.len() {
+            buf[len] = bytes[k];
+            len += 1;
+            k += 1;
+        }
+        p += 1;
+    }
+    len
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(cells: &[(&str, &str)]) -> Row {
+        cells
+            .iter()
+            .map(|(var, cell)| (Into::into(*var), cell.parse().unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn required_and_optional() {
+        let row = row(&[("n", r#""3""#)]);
+
+        assert_eq!(required::<u8, _>(&row, "n", from_term).unwrap(), 3);
+        assert_eq!(optional::<u8, _>(&row, "m", from_term).unwrap(), None);
+
+        let err = required::<u8, _>(&row, "m", from_term).unwrap_err();
+        assert_eq!(err.to_string(), "missing variable `m`");
+
+        let err = required(&row, "n", |_| Err::<u8, _>("nope")).unwrap_err();
+        assert_eq!(err.to_string(), "variable `n`: nope");
+    }
+
+    #[test]
+    fn projection_check() {
+        assert_projected(&["a", "b"], &["b", "c", "a"]);
+        let result = std::panic::catch_unwind(|| assert_projected(&["a", "d"], &["a"]));
+        let message = *result.unwrap_err().downcast::<String>().unwrap();
+        assert!(message.contains("no `?d` variable"), "{message}");
+    }
+}
@@ -1,32 +1,77 @@
+use std::collections::HashMap;
 use std::io;
+use std::marker::PhantomData;
 use std::pin::Pin;
 use std::sync::Arc;
-use std::collections::HashMap;
 
 use csv_async::AsyncReaderBuilder;
 use futures_util::{stream::Stream, StreamExt, TryStreamExt};
+use serde::de::DeserializeOwned;
 use thiserror::Error;
 use tokio_util::io::StreamReader;
 
+use super::de::{from_row, RowError};
+use super::format::ResultFormat;
+use super::interop::TermConversionError;
+use super::json;
+use super::row::FromRow;
+use super::status::StatusError;
 use super::term::{ParseTermError, RDFTerm};
+use crate::auth::CredentialError;
 
-/// Error produced by a [`SelectQueryResponse`] stream.
+/// Error produced when running a query or reading its response stream.
+///
+/// Returned by every [`SparqlQuery::run`](crate::SparqlQuery::run), and yielded by the
+/// [`SelectQueryResponse`] and [`ConstructQueryResponse`] streams.
+///
+/// [`ConstructQueryResponse`]: super::ConstructQueryResponse
 #[derive(Debug, Error)]
 pub enum StreamError {
     /// The HTTP request or network transfer failed.
     #[error(transparent)]
     Http(#[from] reqwest::Error),
-    /// The response body could not be parsed as SPARQL TSV.
+    /// The endpoint answered with a non-`2xx` status.
+    #[error(transparent)]
+    Status(#[from] StatusError),
+    /// The credential provider of the endpoint failed.
+    #[error(transparent)]
+    Credentials(#[from] CredentialError),
+    /// The endpoint answered with a `Content-Type` this crate cannot parse for
+    /// the query form, e.g. an HTML error page or CSV for an ASK query.
+    #[error("unsupported response content type: {0}")]
+    UnsupportedContentType(Box<str>),
+    /// The response body could not be parsed.
     #[error("parse error: {0}")]
     Parse(#[from] ParseError),
 }
 
+/// Error produced when a response body cannot be parsed.
+///
+/// Some variants only exist with the `xml` cargo feature, so the enum is
+/// non-exhaustive.
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum ParseError {
     #[error(transparent)]
     Csv(#[from] csv_async::Error),
     #[error(transparent)]
     Term(#[from] ParseTermError),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Rdf(#[from] oxttl::TurtleParseError),
+    #[error("unsupported RDF term: {0}")]
+    UnsupportedTerm(Box<str>),
+    #[error("cannot deserialize row: {0}")]
+    Row(#[from] RowError),
+    #[error(transparent)]
+    Conversion(#[from] TermConversionError),
+    #[cfg(feature = "xml")]
+    #[error(transparent)]
+    Xml(#[from] quick_xml::Error),
+    #[cfg(feature = "xml")]
+    #[error("malformed XML results: {0}")]
+    MalformedXml(Box<str>),
 }
 
 /// A single result row: variable name → RDF term.
@@ -34,12 +79,27 @@ pub enum ParseError {
 /// Variables that are unbound in a given row are absent from the map.
 pub type Row = HashMap<Arc<str>, RDFTerm>;
 
-/// A streaming SPARQL SELECT response received as tab-separated values.
+/// A single result row with [`oxrdf`] terms, as yielded by
+/// [`SelectQueryResponse::into_oxrdf_rows`].
+pub type OxrdfRow = HashMap<Arc<str>, oxrdf::Term>;
+
+pub(super) type RowStream = Pin<Box<dyn Stream<Item = Result<Row, StreamError>> + Send>>;
+
+/// The variables, links and row stream read from the head of a SELECT response.
+type ResponseParts = (Arc<[Arc<str>]>, Option<Box<[Box<str>]>>, RowStream);
+
+/// A streaming SPARQL SELECT response.
 ///
 /// Returned by [`SparqlQuery<SelectQueryString>::run`](crate::SparqlQuery::run).
-/// The [`vars`](SelectQueryResponse::vars) field is populated as soon as the first
-/// line of the response is received. Rows are then yielded one at a time via
-/// [`into_rows`](SelectQueryResponse::into_rows) as they arrive over the network.
+/// The [`vars`](SelectQueryResponse::vars) field is populated as soon as the
+/// header of the response is received: the first line for TSV, or the `head`
+/// element for JSON and XML. Rows are then yielded one at a time via
+/// [`into_rows`](SelectQueryResponse::into_rows) as they arrive over the network,
+/// whatever the [`ResultFormat`].
+///
+/// Rows read from CSV results have inferred term kinds; each such term has
+/// [`RDFTerm::inferred`] set, and [`is_lossy`](SelectQueryResponse::is_lossy)
+/// reports it for the whole response.
 ///
 /// # Example
 ///
@@ -62,18 +122,66 @@ pub type Row = HashMap<Arc<str>, RDFTerm>;
 /// }
 /// # Ok(()) }
 /// ```
-pub struct SelectQueryResponse {
+pub struct SelectQueryResponse<R = Row> {
     /// The projected variable names from the query's SELECT clause.
     pub vars: Arc<[Arc<str>]>,
-    rows: Pin<Box<dyn Stream<Item = Result<Row, StreamError>> + Send>>,
+    /// The `link` IRIs from the response head, pointing to metadata about the results.
+    ///
+    /// Only the JSON and XML formats carry links; it is `None` for TSV and CSV.
+    pub link: Option<Box<[Box<str>]>>,
+    format: ResultFormat,
+    rows: RowStream,
+    row: PhantomData<fn() -> R>,
 }
 
 impl SelectQueryResponse {
-    pub(crate) async fn from_response(response: reqwest::Response) -> Result<Self, StreamError> {
-        let byte_stream = response.bytes_stream().map(|r| r.map_err(io::Error::other));
-        let stream_reader = StreamReader::new(byte_stream);
+    /// Parses `response` according to its `Content-Type`, or as `requested`
+    /// when the endpoint did not send one.
+    pub(crate) async fn from_response(
+        response: reqwest::Response,
+        requested: ResultFormat,
+    ) -> Result<Self, StreamError> {
+        let format = requested.negotiated_select(&response)?;
+        let (vars, link, rows) = match format {
+            ResultFormat::Tsv => Self::from_tsv(response).await?,
+            ResultFormat::Csv => Self::from_csv(response).await?,
+            ResultFormat::Json => json::select(Box::pin(response.bytes_stream())).await?,
+            #[cfg(feature = "xml")]
+            ResultFormat::Xml => {
+                let byte_stream = response.bytes_stream().map(|r| r.map_err(io::Error::other));
+                super::xml::select(StreamReader::new(byte_stream)).await?
+            }
+        };
+        Ok(Self {
+            vars,
+            link,
+            format,
+            rows,
+            row: PhantomData,
+        })
+    }
+
+    async fn from_tsv(response: reqwest::Response) -> Result<ResponseParts, StreamError> {
         let mut builder = AsyncReaderBuilder::new();
         builder.delimiter(b'\t').quoting(false);
+        Self::from_delimited(response, builder, |cell| Ok(cell.parse::<RDFTerm>()?)).await
+    }
+
+    async fn from_csv(response: reqwest::Response) -> Result<ResponseParts, StreamError> {
+        Self::from_delimited(response, AsyncReaderBuilder::new(), |cell| {
+            Ok(RDFTerm::infer_from_csv(cell))
+        })
+        .await
+    }
+
+    /// Reads TSV or CSV results, turning each non-empty cell into a term with `parse_cell`.
+    async fn from_delimited(
+        response: reqwest::Response,
+        builder: AsyncReaderBuilder,
+        parse_cell: fn(&str) -> Result<RDFTerm, ParseError>,
+    ) -> Result<ResponseParts, StreamError> {
+        let byte_stream = response.bytes_stream().map(|r| r.map_err(io::Error::other));
+        let stream_reader = StreamReader::new(byte_stream);
         let mut csv_reader = builder.create_reader(stream_reader);
 
         let headers = csv_reader
@@ -91,31 +199,278 @@ impl SelectQueryResponse {
         let rows = Box::pin(csv_reader.into_records().map(move |record| {
             let record = record.map_err(ParseError::from)?;
             let mut row = Row::with_capacity(vars_cloned.len());
-            for (var, cell) in vars_cloned.iter().cloned().zip(record.into_iter()) {
+            for (var, cell) in vars_cloned.iter().cloned().zip(&record) {
                 if !cell.is_empty() {
-                    let term = cell.parse::<RDFTerm>().map_err(ParseError::from)?;
-                    row.insert(var, term);
+                    row.insert(var, parse_cell(cell)?);
                 }
             }
             Ok(row)
         }));
 
-        Ok(Self { vars, rows })
+        Ok((vars, None, rows))
+    }
+
+    /// Changes the type [`into_rows`](SelectQueryResponse::into_rows) yields.
+    pub(crate) fn with_row_type<R>(self) -> SelectQueryResponse<R> {
+        SelectQueryResponse {
+            vars: self.vars,
+            link: self.link,
+            format: self.format,
+            rows: self.rows,
+            row: PhantomData,
+        }
+    }
+}
+
+impl<R: From<Row>> SelectQueryResponse<R> {
+    /// Returns the format the results were parsed from, as announced by the
+    /// endpoint's `Content-Type`.
+    pub fn format(&self) -> ResultFormat {
+        self.format
+    }
+
+    /// Returns `true` if the term kinds in this response were inferred rather
+    /// than read from the results, which is the case for
+    /// [`ResultFormat::Csv`].
+    ///
+    /// See [`ResultFormat::is_lossy`] for what is lost. The terms themselves
+    /// carry the same mark in [`RDFTerm::inferred`], which survives collecting
+    /// the rows.
+    pub fn is_lossy(&self) -> bool {
+        self.format.is_lossy()
     }
 
     /// Consumes this value and returns the row stream.
     ///
+    /// Rows are yielded as `R`: a [`Row`] map, or for queries built with
+    /// [`query!`](crate::query!), a struct with a field per projected variable.
     /// Use [`vars`](SelectQueryResponse::vars) before calling this if you need
     /// the projected variable names.
-    pub fn into_rows(self) -> impl Stream<Item = Result<Row, StreamError>> {
+    pub fn into_rows(self) -> impl Stream<Item = Result<R, StreamError>> {
+        self.rows.map_ok(R::from)
+    }
+
+    /// Consumes this value and returns a stream of rows deserialized into `T`.
+    ///
+    /// Each row is converted with [`from_row`], which maps variables onto struct
+    /// fields by name and parses literals into the field types. A row that does
+    /// not fit `T` is yielded as [`ParseError::Row`]; the stream carries on with
+    /// the next row.
+    ///
+    /// ```no_run
+    /// use futures_util::TryStreamExt;
+    /// use serde::Deserialize;
+    /// use sparql_http_client::{Endpoint, SparqlClient, query};
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Country {
+    ///     #[serde(rename = "countryLabel")]
+    ///     label: String,
+    ///     population: Option<u64>,
+    /// }
+    ///
+    /// # #[tokio::main] async fn main() -> anyhow::Result<()> {
+    /// let endpoint = Endpoint::new(SparqlClient::default(), "https://example.org/sparql");
+    /// let countries: Vec<Country> = query!(endpoint, "SELECT ?countryLabel ?population WHERE { ?c ?p ?countryLabel }")
+    ///     .run()
+    ///     .await?
+    ///     .into_typed()
+    ///     .try_collect()
+    ///     .await?;
+    /// # Ok(()) }
+    /// ```
+    pub fn into_typed<T: DeserializeOwned>(self) -> impl Stream<Item = Result<T, StreamError>> {
+        self.rows
+            .map(|row| Ok(from_row(&row?).map_err(ParseError::from)?))
+    }
+
+    /// Consumes this value and returns a stream of rows converted with [`FromRow`].
+    ///
+    /// A row that cannot be converted is yielded as [`ParseError::Row`]; the
+    /// stream carries on with the next row.
+    pub fn into_rows_as<T: FromRow>(self) -> impl Stream<Item = Result<T, StreamError>> {
         self.rows
+            .map(|row| Ok(T::from_row(&row?).map_err(ParseError::from)?))
+    }
+
+    /// Consumes this value and returns a stream of rows with [`oxrdf`] terms,
+    /// ready for an oxigraph store or other crates of the `oxrdf` ecosystem.
+    ///
+    /// A row with a term `oxrdf` rejects, such as an invalid IRI, is yielded
+    /// as [`ParseError::Conversion`]; the stream carries on with the next row.
+    ///
+    /// [`into_rows`](SelectQueryResponse::into_rows) keeps yielding [`Row`]
+    /// whatever the enabled features, so that one dependency cannot change the
+    /// row type another one sees; this stream is the way to get `oxrdf` terms.
+    pub fn into_oxrdf_rows(self) -> impl Stream<Item = Result<OxrdfRow, StreamError>> {
+        self.rows.map(|row| {
+            row?.into_iter()
+                .map(|(var, term)| {
+                    let term = oxrdf::Term::try_from(term).map_err(ParseError::from)?;
+                    Ok((var, term))
+                })
+                .collect()
+        })
     }
 
     /// Collects all rows into a [`Vec`], consuming this response.
     ///
     /// Returns an error if any row fails to parse or if the HTTP transfer fails.
-    pub async fn collect(self) -> Result<Vec<Row>, StreamError> {
-        self.rows.try_collect().await
+    pub async fn collect(self) -> Result<Vec<R>, StreamError> {
+        self.into_rows().try_collect().await
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::response::LiteralType;
+    use crate::response::RDFType;
+
+    fn response(content_type: &str, body: &'static str) -> reqwest::Response {
+        http::Response::builder()
+            .header("content-type", content_type)
+            .body(body)
+            .unwrap()
+            .into()
+    }
+
+    #[tokio::test]
+    async fn csv_rows() -> anyhow::Result<()> {
+        let body = "s,label,n\r\n\
+            http://example.org/a,\"Hello, \"\"world\"\"\",1\r\n\
+            _:b0,,http://example.org/not-really-an-iri\r\n";
+        let response =
+            SelectQueryResponse::from_response(response("text/csv", body), ResultFormat::Csv)
+                .await?;
+
+        assert!(response.is_lossy());
+        assert_eq!(
+            &*response.vars,
+            [Arc::from("s"), "label".into(), "n".into()]
+        );
+
+        let rows = response.collect().await?;
+        assert_eq!(rows.len(), 2);
+        assert!(rows[0]["s"].is_iri());
+        assert!(rows[0].values().all(|term| term.inferred));
+        assert_eq!(&*rows[0]["label"].value, r#"Hello, "world""#);
+        assert_eq!(rows[0]["n"].kind, RDFType::Literal(LiteralType::Plain));
+        assert!(rows[1]["s"].is_blank_node());
+        assert_eq!(&*rows[1]["s"].value, "b0");
+        assert!(!rows[1].contains_key("label"));
+        assert!(rows[1]["n"].is_iri());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn parse_format_sent_by_endpoint() -> anyhow::Result<()> {
+        let body = r#"{"head":{"vars":["s"]},"results":{"bindings":[
+            {"s":{"type":"literal","value":"http://example.org/a"}}
+        ]}}"#;
+        let response = SelectQueryResponse::from_response(
+            response("application/sparql-results+json", body),
+            ResultFormat::Csv,
+        )
+        .await?;
+
+        assert_eq!(response.format(), ResultFormat::Json);
+        assert!(!response.is_lossy());
+        assert!(response.collect().await?[0]["s"].is_literal());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn oxrdf_rows() -> anyhow::Result<()> {
+        let body = "?s\t?label\n<http://example.org/a>\t\"chat\"@fr\n<not an iri>\t\n";
+        let response = SelectQueryResponse::from_response(
+            response("text/tab-separated-values", body),
+            ResultFormat::Tsv,
+        )
+        .await?;
+
+        let rows: Vec<_> = response.into_oxrdf_rows().collect().await;
+        let row = rows[0].as_ref().unwrap();
+        assert_eq!(
+            row["s"],
+            oxrdf::NamedNode::new("http://example.org/a")?.into()
+        );
+        assert_eq!(
+            row["label"],
+            oxrdf::Literal::new_language_tagged_literal("chat", "fr")?.into()
+        );
+        assert!(matches!(
+            rows[1],
+            Err(StreamError::Parse(ParseError::Conversion(_)))
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn reject_html() {
+        let result = SelectQueryResponse::from_response(
+            response("text/html; charset=utf-8", "<html></html>"),
+            ResultFormat::Tsv,
+        )
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(StreamError::UnsupportedContentType(t)) if &*t == "text/html; charset=utf-8"
+        ));
+    }
+
+    #[tokio::test]
+    async fn typed_rows() -> anyhow::Result<()> {
+        #[derive(Debug, serde::Deserialize, PartialEq)]
+        struct Item {
+            s: String,
+            n: Option<u32>,
+        }
+
+        let body =
+            "?s\t?n\n<http://example.org/a>\t\"1\"^^<http://www.w3.org/2001/XMLSchema#int>\n\
+            <http://example.org/b>\t\n\
+            <http://example.org/c>\t\"x\"\n";
+        let response = SelectQueryResponse::from_response(
+            response("text/tab-separated-values", body),
+            ResultFormat::Tsv,
+        )
+        .await?;
+        let items: Vec<_> = response.into_typed::<Item>().collect().await;
+
+        assert_eq!(items.len(), 3);
+        assert_eq!(
+            items[0].as_ref().unwrap(),
+            &Item {
+                s: "http://example.org/a".into(),
+                n: Some(1)
+            }
+        );
+        assert_eq!(items[1].as_ref().unwrap().n, None);
+        assert!(matches!(
+            items[2],
+            Err(StreamError::Parse(ParseError::Row(_)))
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn tsv_is_not_lossy() -> anyhow::Result<()> {
+        let body = "?s\n<http://example.org/a>\n";
+        let response = SelectQueryResponse::from_response(
+            response("text/tab-separated-values", body),
+            ResultFormat::Tsv,
+        )
+        .await?;
+
+        assert!(!response.is_lossy());
+        assert!(response.collect().await?[0]["s"].is_iri());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,111 @@
+use reqwest::{header::HeaderMap, Response, StatusCode};
+use thiserror::Error;
+
+/// The endpoint answered with a non-`2xx` HTTP status.
+///
+/// Carried by [`StreamError::Status`](super::StreamError::Status) and
+/// [`UpdateError::Status`](super::UpdateError::Status). The `body` holds the
+/// endpoint's own explanation, which is usually the most useful part: a query
+/// parser message for `400 Bad Request`, or a timeout notice for `500`/`503`.
+///
+/// # Example
+///
+/// ```no_run
+/// use sparql_http_client::{Endpoint, SparqlClient, SelectQueryString, StreamError};
+///
+/// # #[tokio::main] async fn main() -> anyhow::Result<()> {
+/// let qs: SelectQueryString = "SELECT ?s WHERE { ?s ?p ?o }".parse()?;
+/// let result = Endpoint::new(SparqlClient::default(), "https://example.org/sparql")
+///     .build_query(qs)
+///     .run()
+///     .await;
+///
+/// match result {
+///     Err(StreamError::Status(e)) if e.is_client_error() => eprintln!("rejected: {}", e.body),
+///     Err(StreamError::Status(e)) => eprintln!("server error {}: {}", e.status, e.body),
+///     Err(e) => eprintln!("network or parse failure: {e}"),
+///     Ok(_) => {}
+/// }
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Error)]
+#[error("endpoint returned {status}: {body}")]
+pub struct StatusError {
+    /// The HTTP status code.
+    pub status: StatusCode,
+    /// The response headers, e.g. `Retry-After` on `503 Service Unavailable`.
+    pub headers: HeaderMap,
+    /// The response body decoded as text.
+    pub body: Box<str>,
+}
+
+impl StatusError {
+    /// Returns `true` for `4xx` statuses, which usually mean the request itself was rejected.
+    pub fn is_client_error(&self) -> bool {
+        self.status.is_client_error()
+    }
+
+    /// Returns `true` for `5xx` statuses, which usually mean the endpoint failed or timed out.
+    pub fn is_server_error(&self) -> bool {
+        self.status.is_server_error()
+    }
+
+    /// Passes successful responses through, and turns any other status into a
+    /// [`StatusError`] after reading the body.
+    pub(crate) async fn check<E>(response: Response) -> Result<Response, E>
+    where
+        E: From<StatusError> + From<reqwest::Error>,
+    {
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+
+        let headers = response.headers().clone();
+        let body = response.text().await?.into_boxed_str();
+        Err(StatusError {
+            status,
+            headers,
+            body,
+        }
+        .into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::response::StreamError;
+
+    fn response(status: u16, body: &'static str) -> Response {
+        http::Response::builder()
+            .status(status)
+            .header("retry-after", "120")
+            .body(body)
+            .unwrap()
+            .into()
+    }
+
+    #[tokio::test]
+    async fn success_passes_through() -> anyhow::Result<()> {
+        let response = StatusError::check::<StreamError>(response(200, "ok")).await?;
+        assert_eq!(response.text().await?, "ok");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn failure_keeps_status_headers_and_body() {
+        let err = StatusError::check::<StreamError>(response(400, "Parse error: line 1"))
+            .await
+            .unwrap_err();
+
+        let StreamError::Status(err) = err else {
+            panic!("expected a status error, got {err:?}");
+        };
+        assert!(err.is_client_error());
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+        assert_eq!(err.headers["retry-after"], "120");
+        assert_eq!(&*err.body, "Parse error: line 1");
+    }
+}
@@ -1,8 +1,12 @@
+use std::borrow::Cow;
+use std::fmt;
 use std::str::FromStr;
 
 use serde::{de, Deserialize, Deserializer};
 use thiserror::Error;
 
+use super::triple::Triple;
+
 /// Error returned when parsing a TSV-encoded RDF term fails.
 #[derive(Debug, Error, PartialEq, Eq)]
 pub enum ParseTermError {
@@ -24,16 +28,108 @@ pub enum ParseTermError {
     InvalidDatatypeIri(Box<str>),
     #[error("unexpected suffix after literal: {0:?}")]
     UnexpectedLiteralSuffix(Box<str>),
+    #[error("unterminated triple term")]
+    UnterminatedTripleTerm,
+    #[error("invalid base direction: {0:?}")]
+    InvalidBaseDirection(Box<str>),
 }
 
 /// A single RDF term: the value bound to a variable in one result row.
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// Terms compare and hash by `value` and `kind` only, so an inferred term
+/// equals the same term read from a format that keeps kinds.
+#[derive(Debug, Clone)]
 pub struct RDFTerm {
     pub value: Box<str>,
     pub kind: RDFType,
+    /// `true` if `kind` was guessed from the value rather than read from the
+    /// results, as for every term of [`ResultFormat::Csv`](super::ResultFormat::Csv).
+    pub inferred: bool,
+}
+
+impl PartialEq for RDFTerm {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value && self.kind == other.kind
+    }
+}
+
+impl Eq for RDFTerm {}
+
+impl std::hash::Hash for RDFTerm {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+        self.kind.hash(state);
+    }
 }
 
 impl RDFTerm {
+    /// Creates an IRI term.
+    pub fn iri(iri: impl Into<Box<str>>) -> Self {
+        RDFTerm {
+            value: iri.into(),
+            kind: RDFType::IRI,
+            inferred: false,
+        }
+    }
+
+    /// Creates a plain literal with no language tag or datatype.
+    pub fn literal(value: impl Into<Box<str>>) -> Self {
+        RDFTerm {
+            value: value.into(),
+            kind: RDFType::Literal(LiteralType::Plain),
+            inferred: false,
+        }
+    }
+
+    /// Creates a language-tagged literal such as `"chat"@fr`. The tag is
+    /// lowercased.
+    pub fn lang_literal(value: impl Into<Box<str>>, lang: impl Into<Box<str>>) -> Self {
+        RDFTerm {
+            value: value.into(),
+            kind: RDFType::Literal(LiteralType::Lang(normalize_lang(lang.into()))),
+            inferred: false,
+        }
+    }
+
+    /// Creates a language-tagged literal with a base direction, such as
+    /// `"سلام"@ar--rtl`. The tag is lowercased.
+    pub fn dir_lang_literal(
+        value: impl Into<Box<str>>,
+        lang: impl Into<Box<str>>,
+        direction: BaseDirection,
+    ) -> Self {
+        RDFTerm {
+            value: value.into(),
+            kind: RDFType::Literal(LiteralType::DirLang(normalize_lang(lang.into()), direction)),
+            inferred: false,
+        }
+    }
+
+    /// Creates a literal with the given datatype IRI.
+    pub fn typed_literal(value: impl Into<Box<str>>, datatype: impl Into<Box<str>>) -> Self {
+        RDFTerm {
+            value: value.into(),
+            kind: RDFType::Literal(LiteralType::Datatype(datatype.into())),
+            inferred: false,
+        }
+    }
+
+    /// Creates a triple term (`<< s p o >>`), as found in RDF-star and
+    /// RDF 1.2 results. Its `value` is its N-Triples form.
+    pub fn triple(subject: RDFTerm, predicate: RDFTerm, object: RDFTerm) -> Self {
+        let mut term = RDFTerm {
+            value: Box::default(),
+            kind: RDFType::Triple(Box::new(Triple {
+                subject,
+                predicate,
+                object,
+            })),
+            inferred: false,
+        };
+        term.value = term.to_string().into();
+        term
+    }
+
     pub fn is_iri(&self) -> bool {
         matches!(self.kind, RDFType::IRI)
     }
@@ -46,10 +142,35 @@ impl RDFTerm {
         matches!(self.kind, RDFType::BlankNode)
     }
 
-    /// Returns the language tag if this is a language-tagged literal.
+    pub fn is_triple(&self) -> bool {
+        matches!(self.kind, RDFType::Triple(_))
+    }
+
+    /// Returns the subject, predicate and object if this is a triple term.
+    pub fn as_triple(&self) -> Option<&Triple> {
+        match &self.kind {
+            RDFType::Triple(triple) => Some(triple),
+            _ => None,
+        }
+    }
+
+    /// Returns the language tag if this is a language-tagged literal, with or
+    /// without a base direction.
+    ///
+    /// Tags are lowercased when parsed, so they can be compared with `==`
+    /// against lowercase tags.
     pub fn lang(&self) -> Option<&str> {
         match &self.kind {
-            RDFType::Literal(LiteralType::Lang(lang)) => Some(lang),
+            RDFType::Literal(LiteralType::Lang(lang) | LiteralType::DirLang(lang, _)) => Some(lang),
+            _ => None,
+        }
+    }
+
+    /// Returns the base direction if this is a directional language-tagged
+    /// literal.
+    pub fn direction(&self) -> Option<BaseDirection> {
+        match &self.kind {
+            RDFType::Literal(LiteralType::DirLang(_, direction)) => Some(*direction),
             _ => None,
         }
     }
@@ -62,19 +183,53 @@ impl RDFTerm {
         }
     }
 
-    /// Caller must guarantee `s` starts with `<` and ends with `>`.
-    fn from_bracketed_iri(s: &str) -> Self {
+    /// Classifies an unannotated CSV cell.
+    ///
+    /// `_:`-prefixed cells are blank nodes and cells that parse as an absolute
+    /// IRI are IRIs; everything else is a plain literal. The term is marked
+    /// as [`inferred`](RDFTerm::inferred).
+    pub(crate) fn infer_from_csv(s: &str) -> Self {
+        if let Some(label) = s.strip_prefix("_:") {
+            return RDFTerm {
+                value: label.into(),
+                kind: RDFType::BlankNode,
+                inferred: true,
+            };
+        }
+        let kind = if oxrdf::NamedNode::new(s).is_ok() {
+            RDFType::IRI
+        } else {
+            RDFType::Literal(LiteralType::Plain)
+        };
         RDFTerm {
-            value: s[1..s.len() - 1].into(),
-            kind: RDFType::IRI,
+            value: s.into(),
+            kind,
+            inferred: true,
         }
     }
 
+    /// Serializes the term in N-Triples syntax, which [`FromStr`] parses back.
+    ///
+    /// Same as its [`Display`](fmt::Display) form.
+    pub fn to_ntriples(&self) -> String {
+        self.to_string()
+    }
+
+    /// Caller must guarantee `s` starts with `<` and ends with `>`.
+    fn from_bracketed_iri(s: &str) -> Result<Self, ParseTermError> {
+        Ok(RDFTerm {
+            value: unescape_iri(&s[1..s.len() - 1])?.into(),
+            kind: RDFType::IRI,
+            inferred: false,
+        })
+    }
+
     /// Caller must guarantee `s` starts with `_:`.
     fn from_prefixed_blank_node(s: &str) -> Self {
         RDFTerm {
             value: s[2..].into(),
             kind: RDFType::BlankNode,
+            inferred: false,
         }
     }
 
@@ -84,8 +239,17 @@ impl RDFTerm {
         Ok(RDFTerm {
             value: value.into(),
             kind: RDFType::Literal(parse_literal_type(rest)?),
+            inferred: false,
         })
     }
+
+    /// Caller must guarantee `s` starts with `<<`.
+    fn from_triple_term(s: &str) -> Result<Self, ParseTermError> {
+        match parse_triple_term(s)? {
+            (term, "") => Ok(term),
+            _ => Err(ParseTermError::UnrecognizedCell(s.into())),
+        }
+    }
 }
 
 impl FromStr for RDFTerm {
@@ -93,7 +257,8 @@ impl FromStr for RDFTerm {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.as_bytes() {
-            [b'<', .., b'>'] => Ok(Self::from_bracketed_iri(s)),
+            [b'<', b'<', ..] => Self::from_triple_term(s),
+            [b'<', .., b'>'] => Self::from_bracketed_iri(s),
             [b'_', b':', ..] => Ok(Self::from_prefixed_blank_node(s)),
             [b'"', ..] => Self::from_quoted_literal(s),
             _ => Err(ParseTermError::UnrecognizedCell(s.into())),
@@ -101,9 +266,157 @@ impl FromStr for RDFTerm {
     }
 }
 
+/// N-Triples syntax: IRIs and literals are escaped as in canonical N-Triples,
+/// so that the output is also a valid SPARQL TSV cell. Triple terms are
+/// written `<< s p o >>`, as RDF-star endpoints do in TSV results.
+///
+/// ```
+/// use sparql_http_client::response::RDFTerm;
+///
+/// let term = RDFTerm::lang_literal("say \"hi\"\n", "en");
+/// assert_eq!(term.to_string(), r#""say \"hi\"\n"@en"#);
+/// assert_eq!(term.to_string().parse::<RDFTerm>()?, term);
+/// # Ok::<(), sparql_http_client::response::ParseTermError>(())
+/// ```
+impl fmt::Display for RDFTerm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            RDFType::IRI => write_iri(f, &self.value),
+            RDFType::BlankNode => write!(f, "_:{}", self.value),
+            RDFType::Triple(triple) => write!(
+                f,
+                "<< {} {} {} >>",
+                triple.subject, triple.predicate, triple.object
+            ),
+            RDFType::Literal(literal) => {
+                write_quoted_str(f, &self.value)?;
+                match literal {
+                    LiteralType::Plain => Ok(()),
+                    LiteralType::Lang(lang) => write!(f, "@{lang}"),
+                    LiteralType::DirLang(lang, direction) => write!(f, "@{lang}--{direction}"),
+                    LiteralType::Datatype(dt) => {
+                        f.write_str("^^")?;
+                        write_iri(f, dt)
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Writes `<iri>`, escaping the characters N-Triples forbids in IRIs as `\uXXXX`.
+pub(crate) fn write_iri(f: &mut impl fmt::Write, iri: &str) -> fmt::Result {
+    f.write_char('<')?;
+    for c in iri.chars() {
+        match c {
+            '\0'..=' ' | '<' | '>' | '"' | '{' | '}' | '|' | '^' | '`' | '\\' => {
+                write!(f, "\\u{:04X}", u32::from(c))?
+            }
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('>')
+}
+
+/// Writes `"value"`, escaping as canonical N-Triples does.
+pub(crate) fn write_quoted_str(f: &mut impl fmt::Write, value: &str) -> fmt::Result {
+    f.write_char('"')?;
+    for c in value.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            '\u{8}' => f.write_str("\\b")?,
+            '\u{c}' => f.write_str("\\f")?,
+            '\0'..='\u{1f}' | '\u{7f}' => write!(f, "\\u{:04X}", u32::from(c))?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('"')
+}
+
+/// Plain literal.
+impl From<&str> for RDFTerm {
+    fn from(value: &str) -> Self {
+        Self::literal(value)
+    }
+}
+
+/// Plain literal.
+impl From<String> for RDFTerm {
+    fn from(value: String) -> Self {
+        Self::literal(value)
+    }
+}
+
+/// `xsd:boolean` literal.
+impl From<bool> for RDFTerm {
+    fn from(value: bool) -> Self {
+        Self::typed_literal(value.to_string(), XSD_BOOLEAN)
+    }
+}
+
+macro_rules! integer_terms {
+    ($($t:ty),*) => {
+        $(
+            /// `xsd:integer` literal.
+            impl From<$t> for RDFTerm {
+                fn from(value: $t) -> Self {
+                    Self::typed_literal(value.to_string(), XSD_INTEGER)
+                }
+            }
+        )*
+    };
+}
+
+integer_terms!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// `xsd:double` literal.
+impl From<f64> for RDFTerm {
+    fn from(value: f64) -> Self {
+        Self::typed_literal(xsd_float_lexical(value), XSD_DOUBLE)
+    }
+}
+
+/// `xsd:float` literal.
+impl From<f32> for RDFTerm {
+    fn from(value: f32) -> Self {
+        Self::typed_literal(xsd_float_lexical(value), XSD_FLOAT)
+    }
+}
+
+const XSD_BOOLEAN: &str = "http://www.w3.org/2001/XMLSchema#boolean";
+const XSD_INTEGER: &str = "http://www.w3.org/2001/XMLSchema#integer";
+const XSD_DOUBLE: &str = "http://www.w3.org/2001/XMLSchema#double";
+const XSD_FLOAT: &str = "http://www.w3.org/2001/XMLSchema#float";
+
+/// Formats a float in the XSD lexical space, which spells infinities `INF` and `-INF`.
+///
+/// Finite values are written in the shortest form that reads back as the same
+/// value of their own type, so `0.1f32` gives `0.1`.
+fn xsd_float_lexical<F: Into<f64> + fmt::Debug + Copy>(value: F) -> String {
+    let wide: f64 = value.into();
+    if wide.is_infinite() {
+        if wide > 0.0 { "INF" } else { "-INF" }.to_owned()
+    } else if wide.is_nan() {
+        "NaN".to_owned()
+    } else {
+        format!("{value:?}")
+    }
+}
+
+/// Newtype name under which [`RDFTerm`] asks to be deserialized.
+///
+/// The row deserializer recognizes it and hands over the whole term, as a
+/// sequence of its parts, instead of just its lexical value; other
+/// deserializers see an ordinary newtype.
+pub(crate) const TERM_TOKEN: &str = "$sparql_http_client::RDFTerm";
+
 impl<'de> Deserialize<'de> for RDFTerm {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
-        deserializer.deserialize_str(RDFTermVisitor)
+        deserializer.deserialize_newtype_struct(TERM_TOKEN, RDFTermVisitor)
     }
 }
 
@@ -119,25 +432,168 @@ impl<'de> de::Visitor<'de> for RDFTermVisitor {
     fn visit_str<E: de::Error>(self, s: &str) -> Result<Self::Value, E> {
         s.parse().map_err(E::custom)
     }
+
+    /// Reads the parts the row deserializer hands over: the kind, the value,
+    /// then the language tag and direction, the datatype, or the subject,
+    /// predicate and object of a triple term, and last whether the kind was
+    /// inferred.
+    fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        fn next<'de, T: Deserialize<'de>, A: de::SeqAccess<'de>>(
+            seq: &mut A,
+        ) -> Result<T, A::Error> {
+            seq.next_element()?
+                .ok_or_else(|| de::Error::custom("incomplete RDF term"))
+        }
+
+        let kind: String = next(&mut seq)?;
+        let value: String = next(&mut seq)?;
+        let kind = match kind.as_str() {
+            TERM_IRI => RDFType::IRI,
+            TERM_BLANK_NODE => RDFType::BlankNode,
+            TERM_PLAIN => RDFType::Literal(LiteralType::Plain),
+            TERM_LANG => RDFType::Literal(LiteralType::Lang(next::<String, _>(&mut seq)?.into())),
+            TERM_DIR_LANG => RDFType::Literal(LiteralType::DirLang(
+                next::<String, _>(&mut seq)?.into(),
+                next::<String, _>(&mut seq)?
+                    .parse()
+                    .map_err(de::Error::custom)?,
+            )),
+            TERM_DATATYPE => {
+                RDFType::Literal(LiteralType::Datatype(next::<String, _>(&mut seq)?.into()))
+            }
+            TERM_TRIPLE => RDFType::Triple(Box::new(Triple {
+                subject: next(&mut seq)?,
+                predicate: next(&mut seq)?,
+                object: next(&mut seq)?,
+            })),
+            other => return Err(de::Error::unknown_variant(other, TERM_KINDS)),
+        };
+        Ok(RDFTerm {
+            value: value.into(),
+            kind,
+            inferred: seq.next_element()?.unwrap_or(false),
+        })
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_str(self)
+    }
 }
 
+/// Kinds that start the sequence the row deserializer hands [`RDFTerm`] over as.
+pub(crate) const TERM_IRI: &str = "iri";
+pub(crate) const TERM_BLANK_NODE: &str = "blank_node";
+pub(crate) const TERM_PLAIN: &str = "plain";
+pub(crate) const TERM_LANG: &str = "lang";
+pub(crate) const TERM_DIR_LANG: &str = "dir_lang";
+pub(crate) const TERM_DATATYPE: &str = "datatype";
+pub(crate) const TERM_TRIPLE: &str = "triple";
+const TERM_KINDS: &[&str] = &[
+    TERM_IRI,
+    TERM_BLANK_NODE,
+    TERM_PLAIN,
+    TERM_LANG,
+    TERM_DIR_LANG,
+    TERM_DATATYPE,
+    TERM_TRIPLE,
+];
+
 /// Parses the suffix after a closing `"` into a `LiteralType`.
 fn parse_literal_type(rest: &str) -> Result<LiteralType, ParseTermError> {
     match rest.as_bytes() {
-        [b'@', ..] => Ok(LiteralType::Lang(rest[1..].into())),
+        [b'@', ..] => match rest[1..].split_once("--") {
+            Some((lang, direction)) => lang_literal_type(lang, Some(direction)),
+            None => lang_literal_type(&rest[1..], None),
+        },
         [b'^', b'^', ..] => {
             let dt = &rest[2..];
             let datatype = match dt.as_bytes() {
                 [b'<', .., b'>'] => &dt[1..dt.len() - 1],
                 _ => return Err(ParseTermError::InvalidDatatypeIri(dt.into())),
             };
-            Ok(LiteralType::Datatype(datatype.into()))
+            Ok(LiteralType::Datatype(unescape_iri(datatype)?.into()))
         }
         [] => Ok(LiteralType::Plain),
         _ => Err(ParseTermError::UnexpectedLiteralSuffix(rest.into())),
     }
 }
 
+/// Builds the annotation of a language-tagged literal from its tag and
+/// optional base direction, lowercasing the tag.
+pub(crate) fn lang_literal_type(
+    lang: &str,
+    direction: Option<&str>,
+) -> Result<LiteralType, ParseTermError> {
+    let lang = normalize_lang(lang.into());
+    match direction {
+        Some(direction) => Ok(LiteralType::DirLang(lang, direction.parse()?)),
+        None => Ok(LiteralType::Lang(lang)),
+    }
+}
+
+/// Lowercases a language tag, since BCP 47 tags are case-insensitive.
+fn normalize_lang(mut lang: Box<str>) -> Box<str> {
+    lang.make_ascii_lowercase();
+    lang
+}
+
+/// Parses a `<< s p o >>` or RDF 1.2 `<<( s p o )>>` triple term starting at
+/// `s[0..2] == "<<"`. Returns the term and the remaining input.
+fn parse_triple_term(s: &str) -> Result<(RDFTerm, &str), ParseTermError> {
+    let (rest, close) = match s.strip_prefix("<<(") {
+        Some(rest) => (rest, ")>>"),
+        None => (&s[2..], ">>"),
+    };
+    let (subject, rest) = parse_inner_term(rest)?;
+    let (predicate, rest) = parse_inner_term(rest)?;
+    let (object, rest) = parse_inner_term(rest)?;
+    let rest = rest
+        .trim_start()
+        .strip_prefix(close)
+        .ok_or(ParseTermError::UnterminatedTripleTerm)?;
+    Ok((RDFTerm::triple(subject, predicate, object), rest))
+}
+
+/// Parses the term that follows any whitespace at the start of `s`, inside a
+/// triple term. Returns the term and the remaining input.
+fn parse_inner_term(s: &str) -> Result<(RDFTerm, &str), ParseTermError> {
+    // Blank node labels and language tags end at whitespace or a closing `>>` or `)>>`.
+    let token_len = |s: &str| {
+        s.find(|c: char| c.is_whitespace() || c == '>' || c == ')')
+            .unwrap_or(s.len())
+    };
+    let s = s.trim_start();
+    match s.as_bytes() {
+        [] => Err(ParseTermError::UnterminatedTripleTerm),
+        [b'<', b'<', ..] => parse_triple_term(s),
+        [b'<', ..] => {
+            let end = s.find('>').ok_or(ParseTermError::UnterminatedTripleTerm)? + 1;
+            Ok((RDFTerm::from_bracketed_iri(&s[..end])?, &s[end..]))
+        }
+        [b'_', b':', ..] => {
+            let end = token_len(s);
+            Ok((RDFTerm::from_prefixed_blank_node(&s[..end]), &s[end..]))
+        }
+        [b'"', ..] => {
+            let (value, rest) = parse_quoted_str(s)?;
+            let end = match rest.strip_prefix("^^<") {
+                Some(dt) => dt.find('>').map_or(rest.len(), |i| i + 4),
+                None => token_len(rest),
+            };
+            let term = RDFTerm {
+                value: value.into(),
+                kind: RDFType::Literal(parse_literal_type(&rest[..end])?),
+                inferred: false,
+            };
+            Ok((term, &rest[end..]))
+        }
+        _ => Err(ParseTermError::UnrecognizedCell(s.into())),
+    }
+}
+
 /// Parses a quoted N-Triples-style string starting at `s[0] == '"'`.
 /// Returns the unescaped content and the remaining suffix after the closing `"`.
 fn parse_quoted_str(s: &str) -> Result<(String, &str), ParseTermError> {
@@ -153,6 +609,8 @@ fn parse_quoted_str(s: &str) -> Result<(String, &str), ParseTermError> {
                 Some((_, 'n')) => value.push('\n'),
                 Some((_, 'r')) => value.push('\r'),
                 Some((_, 't')) => value.push('\t'),
+                Some((_, 'b')) => value.push('\u{8}'),
+                Some((_, 'f')) => value.push('\u{c}'),
                 Some((_, '"')) => value.push('"'),
                 Some((_, '\'')) => value.push('\''),
                 Some((_, '\\')) => value.push('\\'),
@@ -165,6 +623,27 @@ fn parse_quoted_str(s: &str) -> Result<(String, &str), ParseTermError> {
     }
 }
 
+/// Unescapes the `\\uXXXX` and `\\UXXXXXXXX` sequences N-Triples allows in IRIs.
+fn unescape_iri(s: &str) -> Result<Cow<'_, str>, ParseTermError> {
+    if !s.contains('\\') {
+        return Ok(Cow::Borrowed(s));
+    }
+    let mut value = String::with_capacity(s.len());
+    let mut chars = s.char_indices();
+    while let Some((_, c)) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                None => return Err(ParseTermError::UnterminatedEscape),
+                Some((_, 'u')) => value.push(parse_unicode_escape(&mut chars, 4)?),
+                Some((_, 'U')) => value.push(parse_unicode_escape(&mut chars, 8)?),
+                Some((_, c)) => return Err(ParseTermError::UnknownEscape(c)),
+            },
+            c => value.push(c),
+        }
+    }
+    Ok(Cow::Owned(value))
+}
+
 fn parse_unicode_escape(
     chars: &mut impl Iterator<Item = (usize, char)>,
     n: usize,
@@ -182,7 +661,7 @@ fn parse_unicode_escape(
 }
 
 /// The type of an RDF term.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum RDFType {
     /// An IRI.
     IRI,
@@ -190,21 +669,87 @@ pub enum RDFType {
     Literal(LiteralType),
     /// A blank node.
     BlankNode,
+    /// A triple term, as returned by RDF-star and RDF 1.2 endpoints. Its
+    /// subject and object may themselves be triple terms.
+    Triple(Box<Triple>),
 }
 
 /// The annotation carried by an RDF literal.
 ///
-/// These three cases are mutually exclusive per the RDF specification.
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// These four cases are mutually exclusive per the RDF specification.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum LiteralType {
     /// A plain literal with no language tag or datatype.
     Plain,
     /// A language-tagged literal (`"..."@lang`).
     Lang(Box<str>),
+    /// A language-tagged literal with a base direction (`"..."@lang--rtl`),
+    /// from RDF 1.2.
+    DirLang(Box<str>, BaseDirection),
     /// A datatyped literal (`"..."^^<datatype>`).
     Datatype(Box<str>),
 }
 
+/// The base direction of a directional language-tagged literal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BaseDirection {
+    /// Left to right (`ltr`).
+    Ltr,
+    /// Right to left (`rtl`).
+    Rtl,
+}
+
+impl FromStr for BaseDirection {
+    type Err = ParseTermError;
+
+    /// Parses `ltr` or `rtl`, in any case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("ltr") {
+            Ok(BaseDirection::Ltr)
+        } else if s.eq_ignore_ascii_case("rtl") {
+            Ok(BaseDirection::Rtl)
+        } else {
+            Err(ParseTermError::InvalidBaseDirection(s.into()))
+        }
+    }
+}
+
+impl BaseDirection {
+    /// Returns the direction as written after `--` in a language tag.
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            BaseDirection::Ltr => "ltr",
+            BaseDirection::Rtl => "rtl",
+        }
+    }
+}
+
+impl fmt::Display for BaseDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "sparql-12")]
+impl From<oxrdf::BaseDirection> for BaseDirection {
+    fn from(direction: oxrdf::BaseDirection) -> Self {
+        match direction {
+            oxrdf::BaseDirection::Ltr => BaseDirection::Ltr,
+            oxrdf::BaseDirection::Rtl => BaseDirection::Rtl,
+        }
+    }
+}
+
+#[cfg(feature = "sparql-12")]
+impl From<BaseDirection> for oxrdf::BaseDirection {
+    fn from(direction: BaseDirection) -> Self {
+        match direction {
+            BaseDirection::Ltr => oxrdf::BaseDirection::Ltr,
+            BaseDirection::Rtl => oxrdf::BaseDirection::Rtl,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use serde_json::Value;
@@ -243,6 +788,23 @@ mod tests {
         assert_eq!(term.lang(), Some("en"));
     }
 
+    #[test]
+    fn parse_directional_lang_literal() {
+        let term = parse_term(r#""مرحبا"@AR-eg--RTL"#).unwrap();
+        assert_eq!(term.lang(), Some("ar-eg"));
+        assert_eq!(term.direction(), Some(BaseDirection::Rtl));
+        assert_eq!(term.to_string(), r#""مرحبا"@ar-eg--rtl"#);
+        assert_eq!(
+            parse_term(r#""hi"@EN"#).unwrap(),
+            RDFTerm::lang_literal("hi", "en")
+        );
+        assert_eq!(parse_term(r#""hi"@en"#).unwrap().direction(), None);
+        assert_eq!(
+            r#""hi"@en--up"#.parse::<RDFTerm>(),
+            Err(ParseTermError::InvalidBaseDirection("up".into()))
+        );
+    }
+
     #[test]
     fn parse_typed_literal() {
         let term = parse_term(r#""42"^^<http://www.w3.org/2001/XMLSchema#integer>"#).unwrap();
@@ -259,6 +821,94 @@ mod tests {
         assert_eq!(&*term.value, r#"hello "world""#);
     }
 
+    #[test]
+    fn ntriples_round_trip() {
+        for cell in [
+            "<http://example.org/>",
+            r"<http://example.org/a\u0020b\u003E>",
+            "_:b0",
+            r#""tab\tquote\"backslash\\""#,
+            r#""bell\u0007form\fbackspace\b""#,
+            r#""hello"@en"#,
+            r#""42"^^<http://www.w3.org/2001/XMLSchema#integer>"#,
+            r#""x"^^<http://example.org/a\u0020b>"#,
+        ] {
+            let term = parse_term(cell).unwrap();
+            assert_eq!(term.to_ntriples(), cell);
+            assert_eq!(term.to_string().parse::<RDFTerm>().unwrap(), term);
+        }
+        let term = parse_term(r#""x"^^<http://example.org/a b>"#).unwrap();
+        assert_eq!(term.datatype(), Some("http://example.org/a b"));
+        assert_eq!(term.to_string().parse::<RDFTerm>().unwrap(), term);
+        assert_eq!(
+            parse_term(r"<http://example.org/\u00E9>").unwrap(),
+            RDFTerm::iri("http://example.org/é")
+        );
+        assert_eq!(
+            r"<http://example.org/\n>".parse::<RDFTerm>(),
+            Err(ParseTermError::UnknownEscape('n'))
+        );
+    }
+
+    #[test]
+    fn parse_triple_terms() {
+        let term = parse_term(
+            r#"<< <http://ex.org/s> <http://ex.org/p> << _:b0 <http://ex.org/q> "a b"@en >> >>"#,
+        )
+        .unwrap();
+        let triple = term.as_triple().unwrap();
+        assert_eq!(triple.subject, RDFTerm::iri("http://ex.org/s"));
+        assert_eq!(triple.predicate, RDFTerm::iri("http://ex.org/p"));
+        let inner = triple.object.as_triple().unwrap();
+        assert!(inner.subject.is_blank_node());
+        assert_eq!(inner.object, RDFTerm::lang_literal("a b", "en"));
+        assert_eq!(term.to_string(), &*term.value);
+        assert_eq!(term.to_string().parse::<RDFTerm>().unwrap(), term);
+
+        let rdf12 = parse_term(
+            r#"<<(<http://ex.org/s> <http://ex.org/p> "1"^^<http://www.w3.org/2001/XMLSchema#integer>)>>"#,
+        )
+        .unwrap();
+        assert_eq!(
+            rdf12.as_triple().unwrap().object.datatype(),
+            Some("http://www.w3.org/2001/XMLSchema#integer")
+        );
+
+        assert_eq!(
+            "<< <http://ex.org/s> <http://ex.org/p> >>".parse::<RDFTerm>(),
+            Err(ParseTermError::UnrecognizedCell(">>".into()))
+        );
+        assert_eq!(
+            "<< <http://ex.org/s> <http://ex.org/p> <http://ex.org/o>".parse::<RDFTerm>(),
+            Err(ParseTermError::UnterminatedTripleTerm)
+        );
+    }
+
+    #[test]
+    fn rust_values() {
+        assert_eq!(RDFTerm::from("a"), RDFTerm::literal("a"));
+        assert_eq!(&*RDFTerm::from(-3i64).value, "-3");
+        assert_eq!(
+            RDFTerm::from(true).datatype(),
+            Some("http://www.w3.org/2001/XMLSchema#boolean")
+        );
+        assert_eq!(&*RDFTerm::from(1.5f64).value, "1.5");
+        assert_eq!(&*RDFTerm::from(f64::NEG_INFINITY).value, "-INF");
+        assert_eq!(&*RDFTerm::from(0.1f32).value, "0.1");
+    }
+
+    #[test]
+    fn infer_csv_kinds() {
+        assert!(RDFTerm::infer_from_csv("http://example.org/a").is_iri());
+        assert!(RDFTerm::infer_from_csv("urn:isbn:0451450523").is_iri());
+        assert!(RDFTerm::infer_from_csv("_:b0").is_blank_node());
+        assert!(RDFTerm::infer_from_csv("42").is_literal());
+        assert!(RDFTerm::infer_from_csv("note: see below").is_literal());
+        assert!(RDFTerm::infer_from_csv("42").inferred);
+        assert!(!"\"42\"".parse::<RDFTerm>().unwrap().inferred);
+        assert_eq!(RDFTerm::infer_from_csv("42"), RDFTerm::literal("42"));
+    }
+
     #[test]
     fn parse_unicode_escape() {
         let term = parse_term(r#""\u0041""#).unwrap();
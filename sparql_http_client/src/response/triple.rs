@@ -0,0 +1,13 @@
+use super::term::RDFTerm;
+
+/// A single RDF statement returned by a graph-producing query.
+///
+/// The subject is an IRI or a blank node and the predicate is always an IRI;
+/// the object may be any [`RDFTerm`]. Triple terms read from RDF-star results
+/// may also have a triple term as subject.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Triple {
+    pub subject: RDFTerm,
+    pub predicate: RDFTerm,
+    pub object: RDFTerm,
+}
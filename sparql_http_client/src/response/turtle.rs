@@ -0,0 +1,230 @@
+use std::fmt::{self, Write as _};
+
+use super::term::{write_iri, write_quoted_str, LiteralType, RDFTerm, RDFType};
+
+const XSD: &str = "http://www.w3.org/2001/XMLSchema#";
+
+/// Prefixes used to shorten IRIs when writing terms with [`RDFTerm::to_turtle`].
+///
+/// Its [`Display`](fmt::Display) form is the matching `@prefix` directives,
+/// one per line, ready to head a Turtle document.
+///
+/// ```
+/// use sparql_http_client::response::{PrefixMap, RDFTerm};
+///
+/// let prefixes = PrefixMap::default()
+///     .with_prefix("ex", "http://example.org/")
+///     .with_prefix("xsd", "http://www.w3.org/2001/XMLSchema#");
+///
+/// assert_eq!(RDFTerm::iri("http://example.org/alice").to_turtle(&prefixes), "ex:alice");
+/// assert_eq!(RDFTerm::iri("http://other.org/bob").to_turtle(&prefixes), "<http://other.org/bob>");
+/// assert_eq!(RDFTerm::from(42).to_turtle(&prefixes), "42");
+/// assert_eq!(
+///     RDFTerm::typed_literal("2024-01-01", "http://www.w3.org/2001/XMLSchema#date").to_turtle(&prefixes),
+///     r#""2024-01-01"^^xsd:date"#
+/// );
+/// assert_eq!(
+///     prefixes.to_string(),
+///     "@prefix ex: <http://example.org/> .\n@prefix xsd: <http://www.w3.org/2001/XMLSchema#> .\n"
+/// );
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PrefixMap {
+    prefixes: Vec<(Box<str>, Box<str>)>,
+}
+
+impl PrefixMap {
+    /// Maps `prefix` to `namespace`, replacing any namespace it was mapped to.
+    ///
+    /// `prefix` must be a valid Turtle prefix name, without the trailing `:`;
+    /// the empty prefix is allowed.
+    pub fn with_prefix(
+        mut self,
+        prefix: impl Into<Box<str>>,
+        namespace: impl Into<Box<str>>,
+    ) -> Self {
+        let (prefix, namespace) = (prefix.into(), namespace.into());
+        match self.prefixes.iter_mut().find(|(p, _)| *p == prefix) {
+            Some((_, ns)) => *ns = namespace,
+            None => self.prefixes.push((prefix, namespace)),
+        }
+        self
+    }
+
+    /// Iterates over the prefixes and their namespaces, in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.prefixes.iter().map(|(p, ns)| (&**p, &**ns))
+    }
+
+    /// Splits `iri` into the prefix of the longest matching namespace and a
+    /// local name, if the local name can be written unescaped in Turtle.
+    pub fn shorten<'a>(&self, iri: &'a str) -> Option<(&str, &'a str)> {
+        self.iter()
+            .filter_map(|(prefix, namespace)| {
+                Some((prefix, namespace.len(), iri.strip_prefix(namespace)?))
+            })
+            .filter(|(_, _, local)| is_local_name(local))
+            .max_by_key(|(_, len, _)| *len)
+            .map(|(prefix, _, local)| (prefix, local))
+    }
+}
+
+impl<P: Into<Box<str>>, N: Into<Box<str>>> FromIterator<(P, N)> for PrefixMap {
+    fn from_iter<I: IntoIterator<Item = (P, N)>>(iter: I) -> Self {
+        iter.into_iter()
+            .fold(Self::default(), |map, (prefix, namespace)| {
+                map.with_prefix(prefix, namespace)
+            })
+    }
+}
+
+impl fmt::Display for PrefixMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (prefix, namespace) in self.iter() {
+            write!(f, "@prefix {prefix}: ")?;
+            write_iri(f, namespace)?;
+            f.write_str(" .\n")?;
+        }
+        Ok(())
+    }
+}
+
+impl RDFTerm {
+    /// Serializes the term in Turtle syntax.
+    ///
+    /// IRIs, datatypes included, are shortened to prefixed names when
+    /// `prefixes` has a matching namespace, and `xsd:integer`, `xsd:decimal`,
+    /// `xsd:double` and `xsd:boolean` literals are written bare when their
+    /// lexical form allows it. Everything else is written as in
+    /// [`to_ntriples`](Self::to_ntriples).
+    pub fn to_turtle(&self, prefixes: &PrefixMap) -> String {
+        let mut out = String::with_capacity(self.value.len() + 2);
+        match &self.kind {
+            RDFType::IRI => write_turtle_iri(&mut out, &self.value, prefixes),
+            RDFType::BlankNode => write!(out, "_:{}", self.value),
+            RDFType::Triple(triple) => write!(
+                out,
+                "<< {} {} {} >>",
+                triple.subject.to_turtle(prefixes),
+                triple.predicate.to_turtle(prefixes),
+                triple.object.to_turtle(prefixes)
+            ),
+            RDFType::Literal(LiteralType::Datatype(dt)) if is_bare_literal(dt, &self.value) => {
+                out.write_str(&self.value)
+            }
+            RDFType::Literal(literal) => {
+                write_quoted_str(&mut out, &self.value).and_then(|()| match literal {
+                    LiteralType::Plain => Ok(()),
+                    LiteralType::Lang(lang) => write!(out, "@{lang}"),
+                    LiteralType::DirLang(lang, direction) => write!(out, "@{lang}--{direction}"),
+                    LiteralType::Datatype(dt) => {
+                        out.write_str("^^")?;
+                        write_turtle_iri(&mut out, dt, prefixes)
+                    }
+                })
+            }
+        }
+        .expect("writing to a String cannot fail");
+        out
+    }
+}
+
+fn write_turtle_iri(out: &mut String, iri: &str, prefixes: &PrefixMap) -> fmt::Result {
+    match prefixes.shorten(iri) {
+        Some((prefix, local)) => write!(out, "{prefix}:{local}"),
+        None => write_iri(out, iri),
+    }
+}
+
+/// Whether a literal can be written without quotes, as a Turtle number or
+/// boolean that reads back with the same datatype and lexical form.
+fn is_bare_literal(datatype: &str, value: &str) -> bool {
+    let digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    fn unsigned(s: &str) -> &str {
+        s.strip_prefix(['+', '-']).unwrap_or(s)
+    }
+    match datatype.strip_prefix(XSD) {
+        Some("integer") => digits(unsigned(value)),
+        Some("decimal") => unsigned(value)
+            .split_once('.')
+            .is_some_and(|(whole, fraction)| {
+                (whole.is_empty() || digits(whole)) && digits(fraction)
+            }),
+        Some("double") => {
+            let Some((mantissa, exponent)) = unsigned(value).split_once(['e', 'E']) else {
+                return false;
+            };
+            let (whole, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+            (digits(whole) || digits(fraction))
+                && (whole.is_empty() || digits(whole))
+                && (fraction.is_empty() || digits(fraction))
+                && digits(unsigned(exponent))
+        }
+        Some("boolean") => matches!(value, "true" | "false"),
+        _ => false,
+    }
+}
+
+/// Whether `local` is a Turtle local name that needs no escaping.
+fn is_local_name(local: &str) -> bool {
+    let mut chars = local.chars();
+    let Some(first) = chars.next() else {
+        return true;
+    };
+    (first.is_alphanumeric() || first == '_' || first == ':')
+        && !local.ends_with('.')
+        && chars.all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | ':' | '\u{b7}'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefixed_names() {
+        let prefixes: PrefixMap = [
+            ("ex", "http://example.org/"),
+            ("exa", "http://example.org/a/"),
+            ("", "http://default.org/"),
+        ]
+        .into_iter()
+        .collect();
+        let turtle = |iri: &str| RDFTerm::iri(iri).to_turtle(&prefixes);
+        assert_eq!(turtle("http://example.org/a/b"), "exa:b");
+        assert_eq!(turtle("http://example.org/a"), "ex:a");
+        assert_eq!(turtle("http://example.org/"), "ex:");
+        assert_eq!(turtle("http://default.org/x.y"), ":x.y");
+        for iri in [
+            "http://example.org/x.",
+            "http://example.org/a?b",
+            "http://example.org/-a",
+        ] {
+            assert_eq!(turtle(iri), format!("<{iri}>"));
+        }
+        let replaced = prefixes.with_prefix("ex", "http://example.com/");
+        assert_eq!(
+            RDFTerm::iri("http://example.org/a").to_turtle(&replaced),
+            "<http://example.org/a>"
+        );
+    }
+
+    #[test]
+    fn literals() {
+        let prefixes = PrefixMap::default();
+        let turtle = |value: &str, datatype: &str| {
+            RDFTerm::typed_literal(value, format!("{XSD}{datatype}")).to_turtle(&prefixes)
+        };
+        assert_eq!(turtle("-5", "integer"), "-5");
+        assert_eq!(turtle(".5", "decimal"), ".5");
+        assert_eq!(turtle("+1.0E3", "double"), "+1.0E3");
+        assert_eq!(turtle("true", "boolean"), "true");
+        assert_eq!(turtle("5.", "decimal"), format!("\"5.\"^^<{XSD}decimal>"));
+        assert_eq!(turtle("1", "boolean"), format!("\"1\"^^<{XSD}boolean>"));
+        assert_eq!(turtle("INF", "double"), format!("\"INF\"^^<{XSD}double>"));
+        assert_eq!(turtle("12", "int"), format!("\"12\"^^<{XSD}int>"));
+        assert_eq!(
+            RDFTerm::lang_literal("line\nbreak", "en").to_turtle(&prefixes),
+            r#""line\nbreak"@en"#
+        );
+    }
+}
@@ -0,0 +1,34 @@
+use reqwest::StatusCode;
+use thiserror::Error;
+
+use super::status::StatusError;
+use crate::auth::CredentialError;
+
+/// The response to a successful SPARQL update.
+///
+/// Returned by [`Endpoint::update`](crate::Endpoint::update) when the endpoint
+/// answers with a `2xx` status. The SPARQL protocol does not define a response
+/// body for updates; some endpoints send a human-readable summary, kept as-is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpdateResponse {
+    /// The HTTP status returned by the endpoint.
+    pub status: StatusCode,
+    /// The response body, often empty.
+    pub body: Box<str>,
+}
+
+/// Error returned by [`Endpoint::update`](crate::Endpoint::update).
+#[derive(Debug, Error)]
+pub enum UpdateError {
+    /// The HTTP request or network transfer failed.
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+    /// The endpoint answered with a non-`2xx` status.
+    ///
+    /// The error body typically holds a parse or permission error message.
+    #[error(transparent)]
+    Status(#[from] StatusError),
+    /// The credential provider of the endpoint failed.
+    #[error(transparent)]
+    Credentials(#[from] CredentialError),
+}
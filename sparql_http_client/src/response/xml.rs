@@ -0,0 +1,441 @@
+use std::collections::VecDeque;
+use std::mem;
+use std::sync::Arc;
+
+use futures_util::stream;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::name::{Namespace, ResolveResult};
+use quick_xml::NsReader;
+use tokio::io::AsyncBufRead;
+
+use super::ask::{AskHead, AskQueryResponse};
+use super::select::{ParseError, Row, RowStream, StreamError};
+use super::term::{lang_literal_type, LiteralType, RDFTerm, RDFType};
+
+type XmlBinding = Vec<(Box<str>, RDFTerm)>;
+
+/// The namespace of the `its:dir` attribute that carries base directions.
+const ITS: &[u8] = b"http://www.w3.org/2005/11/its";
+
+enum XmlEvent {
+    Head {
+        vars: Vec<Box<str>>,
+        link: Vec<Box<str>>,
+    },
+    Result(XmlBinding),
+    Boolean(bool),
+}
+
+/// An incremental reader over a SPARQL Query Results XML document.
+///
+/// Elements are matched by local name, so documents using a prefix for the
+/// `http://www.w3.org/2005/sparql-results#` namespace are accepted too. The
+/// ITS `dir` attribute is matched by namespace, whatever its prefix.
+struct XmlEvents<R> {
+    reader: NsReader<R>,
+    buf: Vec<u8>,
+}
+
+impl<R: AsyncBufRead + Unpin> XmlEvents<R> {
+    fn new(reader: R) -> Self {
+        let mut reader = NsReader::from_reader(reader);
+        reader.config_mut().expand_empty_elements = true;
+        Self {
+            reader,
+            buf: Vec::new(),
+        }
+    }
+
+    /// Reads up to the next start tag, returning `None` once `end` is closed.
+    async fn next_start(&mut self, end: &[u8]) -> Result<Option<BytesStart<'static>>, ParseError> {
+        loop {
+            self.buf.clear();
+            match self.reader.read_event_into_async(&mut self.buf).await? {
+                Event::Start(e) => return Ok(Some(e.into_owned())),
+                Event::End(e) if e.local_name().as_ref() == end => return Ok(None),
+                Event::Eof => return Err(malformed("unexpected end of document")),
+                _ => {}
+            }
+        }
+    }
+
+    async fn next_event(&mut self) -> Result<Option<XmlEvent>, ParseError> {
+        let mut vars = Vec::new();
+        let mut link = Vec::new();
+        loop {
+            self.buf.clear();
+            let start = match self.reader.read_event_into_async(&mut self.buf).await? {
+                Event::Start(e) => e.into_owned(),
+                Event::End(e) if e.local_name().as_ref() == b"head" => {
+                    return Ok(Some(XmlEvent::Head {
+                        vars: mem::take(&mut vars),
+                        link: mem::take(&mut link),
+                    }));
+                }
+                Event::Eof => return Ok(None),
+                _ => continue,
+            };
+            match start.local_name().as_ref() {
+                b"variable" => vars.push(required_attr(&start, b"name")?),
+                b"link" => link.push(required_attr(&start, b"href")?),
+                b"result" => return Ok(Some(XmlEvent::Result(self.result().await?))),
+                b"boolean" => {
+                    let value = self.text(b"boolean").await?;
+                    return match value.trim() {
+                        "true" => Ok(Some(XmlEvent::Boolean(true))),
+                        "false" => Ok(Some(XmlEvent::Boolean(false))),
+                        other => Err(malformed(&format!("invalid boolean: {other:?}"))),
+                    };
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Reads the bindings of one `<result>` element.
+    async fn result(&mut self) -> Result<XmlBinding, ParseError> {
+        let mut bindings = Vec::new();
+        while let Some(start) = self.next_start(b"result").await? {
+            if start.local_name().as_ref() != b"binding" {
+                continue;
+            }
+            let name = required_attr(&start, b"name")?;
+            let term = self.term(b"binding").await?;
+            bindings.push((name, term));
+        }
+        Ok(bindings)
+    }
+
+    /// Reads the single term element inside the element named `end`.
+    async fn term(&mut self, end: &[u8]) -> Result<RDFTerm, ParseError> {
+        let start = self
+            .next_start(end)
+            .await?
+            .ok_or_else(|| malformed("empty binding"))?;
+        let tag = start.local_name();
+        let kind = match tag.as_ref() {
+            b"triple" => {
+                let triple = self.triple().await?;
+                while self.next_start(end).await?.is_some() {}
+                return Ok(triple);
+            }
+            b"uri" => RDFType::IRI,
+            b"bnode" => RDFType::BlankNode,
+            b"literal" => {
+                let lang = attr(&start, b"xml:lang")?;
+                let direction = self.ns_attr(&start, ITS, b"dir")?;
+                let datatype = attr(&start, b"datatype")?;
+                RDFType::Literal(match (lang, datatype) {
+                    (Some(lang), _) => lang_literal_type(&lang, direction.as_deref())?,
+                    (None, Some(dt)) => LiteralType::Datatype(dt),
+                    (None, None) => LiteralType::Plain,
+                })
+            }
+            other => {
+                return Err(ParseError::UnsupportedTerm(
+                    String::from_utf8_lossy(other).into(),
+                ))
+            }
+        };
+        let value = self.text(tag.as_ref()).await?;
+        // Skip to the end of the enclosing element.
+        while self.next_start(end).await?.is_some() {}
+        Ok(RDFTerm {
+            value,
+            kind,
+            inferred: false,
+        })
+    }
+
+    /// Returns the attribute `local` in the `namespace` of the element just
+    /// read, whichever prefix the document binds to that namespace.
+    fn ns_attr(
+        &self,
+        start: &BytesStart<'_>,
+        namespace: &[u8],
+        local: &[u8],
+    ) -> Result<Option<Box<str>>, ParseError> {
+        for attribute in start.attributes() {
+            let attribute = attribute.map_err(quick_xml::Error::from)?;
+            let (resolved, name) = self.reader.resolve_attribute(attribute.key);
+            if resolved == ResolveResult::Bound(Namespace(namespace)) && name.as_ref() == local {
+                return Ok(Some(attribute.unescape_value()?.into()));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Reads the `<subject>`, `<predicate>` and `<object>` of a `<triple>` element.
+    async fn triple(&mut self) -> Result<RDFTerm, ParseError> {
+        let (mut subject, mut predicate, mut object) = (None, None, None);
+        while let Some(start) = self.next_start(b"triple").await? {
+            let part = match start.local_name().as_ref() {
+                b"subject" => &mut subject,
+                b"predicate" => &mut predicate,
+                b"object" => &mut object,
+                _ => continue,
+            };
+            // Triple terms nest, so the recursive call has to be boxed.
+            *part = Some(Box::pin(self.term(start.local_name().as_ref())).await?);
+        }
+        match (subject, predicate, object) {
+            (Some(subject), Some(predicate), Some(object)) => {
+                Ok(RDFTerm::triple(subject, predicate, object))
+            }
+            _ => Err(malformed("incomplete triple term")),
+        }
+    }
+
+    /// Concatenates the character data up to the closing `end` tag.
+    async fn text(&mut self, end: &[u8]) -> Result<Box<str>, ParseError> {
+        let mut text = String::new();
+        loop {
+            self.buf.clear();
+            match self.reader.read_event_into_async(&mut self.buf).await? {
+                Event::Text(e) => text.push_str(&e.unescape()?),
+                Event::CData(e) => text.push_str(&String::from_utf8_lossy(&e)),
+                Event::End(e) if e.local_name().as_ref() == end => return Ok(text.into()),
+                Event::Eof => return Err(malformed("unexpected end of document")),
+                _ => {}
+            }
+        }
+    }
+}
+
+fn attr(start: &BytesStart<'_>, name: &[u8]) -> Result<Option<Box<str>>, ParseError> {
+    match start
+        .try_get_attribute(name)
+        .map_err(quick_xml::Error::from)?
+    {
+        Some(a) => Ok(Some(a.unescape_value()?.into())),
+        None => Ok(None),
+    }
+}
+
+fn required_attr(start: &BytesStart<'_>, name: &[u8]) -> Result<Box<str>, ParseError> {
+    attr(start, name)?.ok_or_else(|| {
+        malformed(&format!(
+            "missing `{}` attribute on <{}>",
+            String::from_utf8_lossy(name),
+            String::from_utf8_lossy(start.local_name().as_ref()),
+        ))
+    })
+}
+
+fn malformed(msg: &str) -> ParseError {
+    ParseError::MalformedXml(msg.into())
+}
+
+fn into_row(binding: XmlBinding, vars: &[Arc<str>]) -> Row {
+    binding
+        .into_iter()
+        .map(|(name, term)| {
+            let var = vars
+                .iter()
+                .find(|v| ***v == *name)
+                .cloned()
+                .unwrap_or_else(|| name.into());
+            (var, term)
+        })
+        .collect()
+}
+
+/// Reads the `<head>` of an XML SELECT response and returns the remaining rows as a stream.
+pub(super) async fn select<R>(
+    reader: R,
+) -> Result<(Arc<[Arc<str>]>, Option<Box<[Box<str>]>>, RowStream), StreamError>
+where
+    R: AsyncBufRead + Unpin + Send + 'static,
+{
+    let mut events = XmlEvents::new(reader);
+    let mut pending = VecDeque::new();
+    let (vars, link) = loop {
+        match events.next_event().await? {
+            Some(XmlEvent::Head { vars, link }) => break (vars, link),
+            Some(XmlEvent::Result(binding)) => pending.push_back(binding),
+            Some(XmlEvent::Boolean(_)) => return Err(malformed("expected SELECT results").into()),
+            None => break (Vec::new(), Vec::new()),
+        }
+    };
+
+    let vars: Arc<[Arc<str>]> = vars.iter().map(|v| Arc::from(&**v)).collect();
+    let link = (!link.is_empty()).then(|| link.into_boxed_slice());
+
+    let vars_cloned = Arc::clone(&vars);
+    let rows = Box::pin(stream::try_unfold(
+        (events, pending),
+        move |(mut events, mut pending)| {
+            let vars = Arc::clone(&vars_cloned);
+            async move {
+                let binding = match pending.pop_front() {
+                    Some(binding) => binding,
+                    None => loop {
+                        match events.next_event().await? {
+                            Some(XmlEvent::Result(binding)) => break binding,
+                            Some(_) => continue,
+                            None => return Ok(None),
+                        }
+                    },
+                };
+                Ok(Some((into_row(binding, &vars), (events, pending))))
+            }
+        },
+    ));
+
+    Ok((vars, link, rows))
+}
+
+/// Reads an XML ASK response.
+pub(super) async fn ask<R>(reader: R) -> Result<AskQueryResponse, StreamError>
+where
+    R: AsyncBufRead + Unpin,
+{
+    let mut events = XmlEvents::new(reader);
+    let mut link = None;
+    loop {
+        match events.next_event().await? {
+            Some(XmlEvent::Head { link: l, .. }) => {
+                link = (!l.is_empty()).then(|| l.into_boxed_slice());
+            }
+            Some(XmlEvent::Boolean(boolean)) => {
+                return Ok(AskQueryResponse {
+                    head: AskHead { link },
+                    boolean,
+                });
+            }
+            Some(XmlEvent::Result(_)) => return Err(malformed("expected a boolean result").into()),
+            None => return Err(malformed("missing <boolean> element").into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::TryStreamExt;
+
+    use super::*;
+    use crate::response::BaseDirection;
+
+    #[tokio::test]
+    async fn select_rows() -> anyhow::Result<()> {
+        let doc = r#"<?xml version="1.0"?>
+            <sparql xmlns="http://www.w3.org/2005/sparql-results#">
+              <head>
+                <variable name="s"/>
+                <variable name="label"/>
+                <variable name="n"/>
+                <link href="http://example.org/meta"/>
+              </head>
+              <results>
+                <result>
+                  <binding name="s"><uri>http://example.org/a?x=1&amp;y=2</uri></binding>
+                  <binding name="label"><literal xml:lang="En"> padded &lt;label&gt; </literal></binding>
+                  <binding name="title">
+                    <literal xml:lang="he" its:dir="rtl" xmlns:its="http://www.w3.org/2005/11/its">כותרת</literal>
+                  </binding>
+                  <binding name="n">
+                    <literal datatype="http://www.w3.org/2001/XMLSchema#integer">1</literal>
+                  </binding>
+                </result>
+                <result>
+                  <binding name="s"><bnode>r2</bnode></binding>
+                  <binding name="label"><literal/></binding>
+                </result>
+              </results>
+            </sparql>"#;
+
+        let (vars, link, rows) = select(doc.as_bytes()).await?;
+        let rows: Vec<Row> = rows.try_collect().await?;
+
+        assert_eq!(&*vars, [Arc::from("s"), "label".into(), "n".into()]);
+        assert_eq!(
+            link.as_deref(),
+            Some(&[Box::from("http://example.org/meta")][..])
+        );
+        assert_eq!(rows.len(), 2);
+        assert_eq!(&*rows[0]["s"].value, "http://example.org/a?x=1&y=2");
+        assert_eq!(&*rows[0]["label"].value, " padded <label> ");
+        assert_eq!(rows[0]["label"].lang(), Some("en"));
+        assert_eq!(rows[0]["title"].direction(), Some(BaseDirection::Rtl));
+        assert_eq!(
+            rows[0]["n"].datatype(),
+            Some("http://www.w3.org/2001/XMLSchema#integer")
+        );
+        assert!(rows[1]["s"].is_blank_node());
+        assert_eq!(&*rows[1]["label"].value, "");
+        assert!(!rows[1].contains_key("n"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn direction_by_namespace() -> anyhow::Result<()> {
+        let doc = r#"<sparql xmlns="http://www.w3.org/2005/sparql-results#"
+                xmlns:i="http://www.w3.org/2005/11/its" xmlns:its="http://example.org/other">
+              <head><variable name="a"/><variable name="b"/></head>
+              <results><result>
+                <binding name="a"><literal xml:lang="ar" i:dir="rtl">نص</literal></binding>
+                <binding name="b"><literal xml:lang="ar" its:dir="rtl">نص</literal></binding>
+              </result></results>
+            </sparql>"#;
+
+        let (_, _, rows) = select(doc.as_bytes()).await?;
+        let rows: Vec<Row> = rows.try_collect().await?;
+
+        assert_eq!(rows[0]["a"].direction(), Some(BaseDirection::Rtl));
+        assert_eq!(rows[0]["b"].direction(), None);
+        assert_eq!(rows[0]["b"].lang(), Some("ar"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn triple_terms() -> anyhow::Result<()> {
+        let doc = r#"<sparql xmlns="http://www.w3.org/2005/sparql-results#">
+              <head><variable name="t"/></head>
+              <results><result><binding name="t"><triple>
+                <subject><uri>http://example.org/s</uri></subject>
+                <predicate><uri>http://example.org/p</uri></predicate>
+                <object><triple>
+                  <subject><bnode>b0</bnode></subject>
+                  <predicate><uri>http://example.org/q</uri></predicate>
+                  <object><literal xml:lang="en">o</literal></object>
+                </triple></object>
+              </triple></binding></result></results>
+            </sparql>"#;
+
+        let (_, _, rows) = select(doc.as_bytes()).await?;
+        let rows: Vec<Row> = rows.try_collect().await?;
+
+        let triple = rows[0]["t"].as_triple().unwrap();
+        assert_eq!(triple.predicate, RDFTerm::iri("http://example.org/p"));
+        let inner = triple.object.as_triple().unwrap();
+        assert!(inner.subject.is_blank_node());
+        assert_eq!(inner.object, RDFTerm::lang_literal("o", "en"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn ask_boolean() -> anyhow::Result<()> {
+        let doc = r#"<sparql xmlns="http://www.w3.org/2005/sparql-results#">
+              <head/>
+              <boolean>true</boolean>
+            </sparql>"#;
+
+        assert!(bool::from(ask(doc.as_bytes()).await?));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn missing_binding_name() {
+        let doc = r#"<sparql><head><variable name="x"/></head><results>
+              <result><binding><uri>http://example.org/</uri></binding></result>
+            </results></sparql>"#;
+
+        let (_, _, rows) = select(doc.as_bytes()).await.unwrap();
+        let err = rows.try_collect::<Vec<_>>().await.unwrap_err();
+        assert!(err.to_string().contains("missing `name`"), "{err}");
+    }
+}
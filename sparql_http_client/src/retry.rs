@@ -0,0 +1,273 @@
+//! Retrying queries that fail with transient errors.
+
+use std::error::Error;
+use std::hash::BuildHasher;
+use std::io;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::StatusCode;
+
+use crate::response::StreamError;
+
+/// When and how often a failed query is sent again.
+///
+/// Set on an endpoint with [`Endpoint::with_retry_policy`]; queries are not
+/// retried otherwise. Updates are never retried, since they are not
+/// idempotent.
+///
+/// A query is retried when the endpoint answers `429 Too Many Requests`,
+/// `502 Bad Gateway`, `503 Service Unavailable` or `504 Gateway Timeout`, or
+/// when the connection could not be established or was reset before a
+/// response arrived. Retrying stops as soon as the endpoint answers with a
+/// success status, so rows that have started streaming are never fetched
+/// twice.
+///
+/// The delay before retry `n` is drawn between half and all of
+/// `initial_backoff * 2^n`, capped at `max_backoff`. A `Retry-After` header is
+/// honored when it asks for a longer delay. No retry is made once it would
+/// end after `budget` has elapsed since the first attempt.
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use sparql_http_client::{Endpoint, RetryPolicy, SparqlClient};
+///
+/// let endpoint = Endpoint::new(SparqlClient::default(), "https://query.wikidata.org/sparql")
+///     .with_retry_policy(
+///         RetryPolicy::default()
+///             .with_max_retries(5)
+///             .with_budget(Duration::from_secs(300)),
+///     );
+/// ```
+///
+/// [`Endpoint::with_retry_policy`]: crate::Endpoint::with_retry_policy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    max_retries: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    budget: Duration,
+}
+
+impl Default for RetryPolicy {
+    /// Three retries, backing off from 500 ms up to 30 s, within two minutes.
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            budget: Duration::from_secs(120),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Sets the number of retries after the first attempt.
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Sets the delay before the first retry, doubled for every later one.
+    pub fn with_initial_backoff(mut self, backoff: Duration) -> Self {
+        self.initial_backoff = backoff;
+        self
+    }
+
+    /// Caps the delay computed by the exponential backoff. A longer
+    /// `Retry-After` is still honored.
+    pub fn with_max_backoff(mut self, backoff: Duration) -> Self {
+        self.max_backoff = backoff;
+        self
+    }
+
+    /// Sets the total time after which no retry is made, counted from the
+    /// first attempt.
+    pub fn with_budget(mut self, budget: Duration) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    pub(crate) fn start(&self) -> Retries<'_> {
+        Retries {
+            policy: self,
+            attempt: 0,
+            started: Instant::now(),
+        }
+    }
+}
+
+/// The retries made for one query.
+pub(crate) struct Retries<'a> {
+    policy: &'a RetryPolicy,
+    attempt: u32,
+    started: Instant,
+}
+
+impl Retries<'_> {
+    /// Returns how long to wait before sending the query again after `error`,
+    /// or `None` if it should be returned instead.
+    pub(crate) fn delay(&mut self, error: &StreamError) -> Option<Duration> {
+        if self.attempt >= self.policy.max_retries {
+            return None;
+        }
+        let retry_after = match error {
+            StreamError::Status(e) if is_transient_status(e.status) => retry_after(&e.headers),
+            StreamError::Http(e) if is_transient_error(e) => None,
+            _ => return None,
+        };
+
+        let backoff = self
+            .policy
+            .initial_backoff
+            .saturating_mul(2u32.saturating_pow(self.attempt))
+            .min(self.policy.max_backoff);
+        let backoff = backoff / 2 + jitter(backoff / 2);
+        let delay = retry_after.map_or(backoff, |after| after.max(backoff));
+
+        if self.started.elapsed() + delay > self.policy.budget {
+            return None;
+        }
+        self.attempt += 1;
+        Some(delay)
+    }
+}
+
+fn is_transient_status(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::TOO_MANY_REQUESTS
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+/// Returns `true` for errors where the request did not reach the endpoint, or
+/// the connection broke before a response arrived.
+fn is_transient_error(error: &reqwest::Error) -> bool {
+    if error.is_connect() {
+        return true;
+    }
+    let mut source = error.source();
+    while let Some(e) = source {
+        if let Some(e) = e.downcast_ref::<io::Error>() {
+            return matches!(
+                e.kind(),
+                io::ErrorKind::ConnectionReset
+                    | io::ErrorKind::ConnectionAborted
+                    | io::ErrorKind::BrokenPipe
+                    | io::ErrorKind::UnexpectedEof
+            );
+        }
+        source = e.source();
+    }
+    false
+}
+
+/// Reads a `Retry-After` header, given either in seconds or as an HTTP date.
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = parse_http_date(value)?;
+    Some(date.duration_since(SystemTime::now()).unwrap_or_default())
+}
+
+/// Parses an HTTP date in the preferred IMF-fixdate format, e.g.
+/// `Sun, 06 Nov 1994 08:49:37 GMT`.
+fn parse_http_date(value: &str) -> Option<SystemTime> {
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+    let [_, day, month, year, time, "GMT"] = value.split(' ').collect::<Vec<_>>()[..] else {
+        return None;
+    };
+    let day: u64 = day.parse().ok()?;
+    let month = MONTHS.iter().position(|m| *m == month)? as u64 + 1;
+    let year: u64 = year.parse().ok()?;
+    let mut time = time.splitn(3, ':').map(|t| t.parse::<u64>().ok());
+    let (hour, minute, second) = (time.next()??, time.next()??, time.next()??);
+
+    // Days since the epoch of a proleptic Gregorian date, counting years
+    // from March so that leap days come last.
+    let (y, m) = if month <= 2 {
+        (year.checked_sub(1)?, month + 9)
+    } else {
+        (year, month - 3)
+    };
+    let days = 365 * y + y / 4 - y / 100 + y / 400 + (153 * m + 2) / 5 + day - 1;
+    let seconds = days.checked_sub(719_468)? * 86_400 + hour * 3600 + minute * 60 + second;
+    Some(UNIX_EPOCH + Duration::from_secs(seconds))
+}
+
+/// Returns a random duration between zero and `max`.
+fn jitter(max: Duration) -> Duration {
+    // Each `RandomState` is seeded with fresh random keys.
+    let random = std::collections::hash_map::RandomState::new().hash_one(Instant::now());
+    max.mul_f64(random as f64 / u64::MAX as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::response::StatusError;
+
+    fn status(code: u16, retry_after: Option<&str>) -> StreamError {
+        let mut headers = HeaderMap::new();
+        if let Some(value) = retry_after {
+            headers.insert(RETRY_AFTER, value.parse().unwrap());
+        }
+        StreamError::Status(StatusError {
+            status: StatusCode::from_u16(code).unwrap(),
+            headers,
+            body: "".into(),
+        })
+    }
+
+    #[test]
+    fn http_date() {
+        let date = parse_http_date("Sun, 06 Nov 1994 08:49:37 GMT").unwrap();
+        assert_eq!(date, UNIX_EPOCH + Duration::from_secs(784_111_777));
+        let date = parse_http_date("Thu, 29 Feb 2024 00:00:00 GMT").unwrap();
+        assert_eq!(date, UNIX_EPOCH + Duration::from_secs(1_709_164_800));
+        assert_eq!(parse_http_date("Sunday, 06-Nov-94 08:49:37 GMT"), None);
+    }
+
+    #[test]
+    fn backoff() {
+        let policy = RetryPolicy::default()
+            .with_max_retries(3)
+            .with_initial_backoff(Duration::from_millis(100))
+            .with_max_backoff(Duration::from_millis(300));
+        let mut retries = policy.start();
+
+        let first = retries.delay(&status(503, None)).unwrap();
+        assert!((50..=100).contains(&first.as_millis()), "{first:?}");
+        let second = retries.delay(&status(502, None)).unwrap();
+        assert!((100..=200).contains(&second.as_millis()), "{second:?}");
+        let third = retries.delay(&status(504, None)).unwrap();
+        assert!((150..=300).contains(&third.as_millis()), "{third:?}");
+        assert_eq!(retries.delay(&status(503, None)), None);
+    }
+
+    #[test]
+    fn retry_after_and_budget() {
+        let policy = RetryPolicy::default().with_budget(Duration::from_secs(10));
+
+        let mut retries = policy.start();
+        assert_eq!(
+            retries.delay(&status(429, Some("7"))),
+            Some(Duration::from_secs(7))
+        );
+        assert_eq!(retries.delay(&status(429, Some("30"))), None);
+
+        let mut retries = policy.start();
+        assert_eq!(retries.delay(&status(400, None)), None);
+        assert_eq!(retries.delay(&status(500, None)), None);
+        let past = "Sun, 06 Nov 1994 08:49:37 GMT";
+        assert!(retries.delay(&status(429, Some(past))).unwrap() <= Duration::from_millis(500));
+    }
+}
@@ -1,15 +1,25 @@
 use proc_macro::TokenStream;
-use proc_macro2::TokenStream as TokenStream2;
-use quote::quote;
-use spargebra::{Query, SparqlParser};
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use spargebra::{algebra::GraphPattern, Query, SparqlParser, Update};
 use syn::{
+    ext::IdentExt,
     parse::{Parse, ParseStream},
-    parse_macro_input, Expr, LitStr, Token,
+    parse_macro_input, Data, DeriveInput, Expr, Fields, Ident, LitStr, Path, Token, Type,
 };
 
+mod syntax_error;
+
 struct MacroInput {
     endpoint: Expr,
     query_str: LitStr,
+    bindings: Vec<Binding>,
+}
+
+/// A `name = value` argument filling the `$name` placeholder of a query.
+struct Binding {
+    name: Ident,
+    value: Expr,
 }
 
 impl Parse for MacroInput {
@@ -17,10 +27,45 @@ impl Parse for MacroInput {
         let endpoint = input.parse::<Expr>()?;
         input.parse::<Token![,]>()?;
         let query_str = input.parse::<LitStr>()?;
-        let _ = input.parse::<Token![,]>();
+        let mut bindings = Vec::new();
+        while input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let name = input.call(Ident::parse_any)?;
+            input.parse::<Token![=]>()?;
+            let value = input.parse::<Expr>()?;
+            bindings.push(Binding { name, value });
+        }
         Ok(Self {
             endpoint,
             query_str,
+            bindings,
+        })
+    }
+}
+
+impl MacroInput {
+    fn reject_bindings(&self, macro_name: &str) -> syn::Result<()> {
+        match self.bindings.first() {
+            Some(binding) => Err(syn::Error::new_spanned(
+                &binding.name,
+                format!("{macro_name} does not accept bindings"),
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+struct QueryAsInput {
+    row_type: Type,
+    input: MacroInput,
+}
+
+impl Parse for QueryAsInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let row_type = input.parse::<Type>()?;
+        input.parse::<Token![,]>()?;
+        Ok(Self {
+            row_type,
+            input: input.parse()?,
         })
     }
 }
@@ -28,17 +73,21 @@ impl Parse for MacroInput {
 fn parse_sparql(query_str: &LitStr) -> syn::Result<Query> {
     SparqlParser::new()
         .parse_query(&query_str.value())
-        .map_err(|e| syn::Error::new_spanned(query_str, format!("SPARQL syntax error: {e}")))
+        .map_err(|e| syntax_error::in_literal(query_str, &e))
+}
+
+fn parse_sparql_update(update_str: &LitStr) -> syn::Result<Update> {
+    SparqlParser::new()
+        .parse_update(&update_str.value())
+        .map_err(|e| syntax_error::in_literal(update_str, &e))
 }
 
-fn query_string_type(parsed: &Query, query_str: &LitStr) -> syn::Result<TokenStream2> {
+fn query_string_type(parsed: &Query) -> TokenStream2 {
     match parsed {
-        Query::Select { .. } => Ok(quote! { ::sparql_http_client::SelectQueryString }),
-        Query::Ask { .. } => Ok(quote! { ::sparql_http_client::AskQueryString }),
-        _ => Err(syn::Error::new_spanned(
-            query_str,
-            "only SELECT and ASK queries are currently supported",
-        )),
+        Query::Select { .. } => quote! { ::sparql_http_client::SelectQueryString },
+        Query::Ask { .. } => quote! { ::sparql_http_client::AskQueryString },
+        Query::Construct { .. } => quote! { ::sparql_http_client::ConstructQueryString },
+        Query::Describe { .. } => quote! { ::sparql_http_client::DescribeQueryString },
     }
 }
 
@@ -53,33 +102,504 @@ fn build_query_expr(endpoint: &Expr, qs_type: TokenStream2, normalized: &str) ->
 /// Creates a `SparqlQuery` with compile-time SPARQL syntax validation.
 ///
 /// The query kind (`SELECT`, `ASK`, …) is resolved at compile time, so the returned value is
-/// already typed as `SelectQuery` or `AskQuery`,
+/// already typed as `SelectQuery`, `AskQuery`, `ConstructQuery` or `DescribeQuery`,
 /// and `.run().await` yields the matching response type with no runtime parsing overhead.
 ///
-/// A malformed or unsupported query kind is a **compile error**.
+/// A malformed query is a **compile error**.
+///
+/// # Rows
+///
+/// For a `SELECT`, the macro also generates a row struct with a public
+/// `Option<RDFTerm>` field per projected variable, and rows are yielded as that
+/// struct instead of a `Row` map. Reading a variable that the query does not
+/// project is therefore a compile error. Variable names that are not Rust
+/// identifiers are adjusted: keywords become raw identifiers (`r#type`) and
+/// other characters are replaced by `_`.
+///
+/// # Bindings
+///
+/// `$name` variables are placeholders, filled in by `name = value` arguments
+/// after the query. Every placeholder must be bound and every binding must name
+/// a variable of the query (`?name` variables may be bound too), or the macro
+/// fails to compile. Values are substituted into the query algebra by
+/// `PreparedQuery`, so they are never interpreted as SPARQL syntax. With
+/// bindings, the macro returns `Result<SparqlQuery<_>, BindError>`, since a
+/// value may still be rejected at runtime, e.g. a literal bound where an IRI is
+/// required.
 ///
 /// # Example
 ///
 /// ```rust,ignore
 /// let query = query!(endpoint, "SELECT ?s WHERE { ?s ?p ?o }");
-/// let response = query.run().await?;
+/// let rows = query.run().await?.collect().await?;
+/// println!("{:?}", rows[0].s);
+///
+/// let query = query!(endpoint, "SELECT ?s WHERE { ?s ?p $name }", name = user_input)?;
 /// ```
 #[proc_macro]
 pub fn query(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as MacroInput);
+
+    let parsed = match parse_sparql(&input.query_str) {
+        Ok(q) => q,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    expand_query(
+        &input,
+        &input.query_str.value(),
+        &parsed,
+        TokenStream2::new(),
+    )
+    .unwrap_or_else(syn::Error::into_compile_error)
+    .into()
+}
+
+/// Creates a `SparqlQuery` from a SPARQL file, validated at compile time.
+///
+/// Takes the endpoint and a path relative to the `CARGO_MANIFEST_DIR` of the
+/// calling crate, typically a `.rq` file. The file is read and checked while
+/// compiling, and everything else works as with [`query!`]: the query kind
+/// selects the returned type, SELECT rows get a generated struct and `$name`
+/// placeholders are bound with `name = value` arguments.
+///
+/// The file is tracked by the compiler, so editing it triggers a rebuild. A
+/// syntax error is reported with the file's line and column.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let rows = query_file!(endpoint, "queries/top_countries.rq", lang = "en")?
+///     .run()
+///     .await?
+///     .collect()
+///     .await?;
+/// ```
+#[proc_macro]
+pub fn query_file(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as MacroInput);
+    expand_query_file(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand_query_file(input: &MacroInput) -> syn::Result<TokenStream2> {
+    let relative = input.query_str.value();
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
+        .map_err(|_| syn::Error::new_spanned(&input.query_str, "CARGO_MANIFEST_DIR is not set"))?;
+    let path = std::path::Path::new(&manifest_dir).join(&relative);
+    let text = std::fs::read_to_string(&path).map_err(|e| {
+        syn::Error::new_spanned(
+            &input.query_str,
+            format!("couldn't read `{}`: {e}", path.display()),
+        )
+    })?;
+
+    let parsed = SparqlParser::new()
+        .parse_query(&text)
+        .map_err(|e| syntax_error::in_file(&input.query_str, &relative, &text, &e))?;
+
+    // `include_str!` registers the file as a dependency of the calling crate.
+    let path = path.to_str().ok_or_else(|| {
+        syn::Error::new_spanned(&input.query_str, "the query file path is not valid UTF-8")
+    })?;
+    let track = quote! { const _: &str = ::core::include_str!(#path); };
+    expand_query(input, &text, &parsed, track)
+}
+
+/// Expands `query!` and `query_file!` once the query `text` has been parsed.
+///
+/// `prelude` is emitted first in the generated block.
+fn expand_query(
+    input: &MacroInput,
+    text: &str,
+    parsed: &Query,
+    prelude: TokenStream2,
+) -> syn::Result<TokenStream2> {
+    let qs_type = query_string_type(parsed);
+    let normalized = parsed.to_string();
+
+    let placeholders = check_bindings(input, text)?;
+    let (row_def, with_row) = match parsed {
+        Query::Select { pattern, .. } => {
+            let (def, row) = row_type(&input.query_str, &projection(pattern))?;
+            (def, quote! { .with_row_type::<#row>() })
+        }
+        _ => (TokenStream2::new(), TokenStream2::new()),
+    };
+    // The caller's expressions are evaluated before the row struct is declared,
+    // in an outer block, so that its name cannot shadow theirs.
+    let endpoint = &input.endpoint;
+    let endpoint_var = Ident::new("endpoint", Span::mixed_site());
+    let query_var = Ident::new("query", Span::mixed_site());
+    if placeholders.is_empty() && input.bindings.is_empty() {
+        return Ok(quote! {
+            {
+                #prelude
+                let #endpoint_var = #endpoint;
+                {
+                    #row_def
+                    #endpoint_var.build_query(
+                        <#qs_type as ::sparql_http_client::QueryString>::new_unchecked(#normalized)
+                    ) #with_row
+                }
+            }
+        });
+    }
+
+    let names = input.bindings.iter().map(|b| b.name.unraw().to_string());
+    let values = input.bindings.iter().map(|b| &b.value);
+    Ok(quote! {
+        {
+            #prelude
+            let #endpoint_var = #endpoint;
+            let #query_var = ::sparql_http_client::PreparedQuery::<#qs_type>::new_unchecked(
+                #normalized,
+                &[#(#placeholders),*],
+            )
+            #(.bind(#names, #values))*
+            .finish();
+            {
+                #row_def
+                #query_var.map(|query| #endpoint_var.build_query(query) #with_row)
+            }
+        }
+    })
+}
+
+/// Generates the row struct of a SELECT query: one public `Option<RDFTerm>`
+/// field per projected variable, converted from a `Row` map. Returns the
+/// definitions and the struct name.
+fn row_type(query_str: &LitStr, vars: &[String]) -> syn::Result<(TokenStream2, Ident)> {
+    let row = Ident::new("Row", Span::mixed_site());
+    let mut fields: Vec<Ident> = Vec::new();
+    for var in vars {
+        let field = field_ident(var);
+        if fields.contains(&field) {
+            return Err(syn::Error::new_spanned(
+                query_str,
+                format!(
+                    "`?{var}` maps to the same row field as another variable; rename it with `AS`"
+                ),
+            ));
+        }
+        fields.push(field);
+    }
+    let docs = vars
+        .iter()
+        .map(|var| format!(" The `?{var}` variable, `None` when unbound."));
+    let def = quote! {
+        /// A result row of the query, generated by `query!` from its projection.
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        #[allow(non_snake_case, dead_code)]
+        struct #row {
+            #(#[doc = #docs] pub #fields: ::core::option::Option<::sparql_http_client::response::RDFTerm>,)*
+        }
+
+        impl ::core::convert::From<::sparql_http_client::Row> for #row {
+            fn from(mut row: ::sparql_http_client::Row) -> Self {
+                Self { #(#fields: row.remove(#vars),)* }
+            }
+        }
+    };
+    Ok((def, row))
+}
+
+/// Turns a SPARQL variable name into a field name: characters Rust does not
+/// allow become `_`, a leading digit gets a `_` prefix and keywords become raw
+/// identifiers, or get a `_` suffix where that is not possible.
+fn field_ident(var: &str) -> Ident {
+    let sanitize = |allowed: fn(char) -> bool| {
+        let mut name: String = var
+            .chars()
+            .map(|c| if allowed(c) || c == '_' { c } else { '_' })
+            .collect();
+        if name.starts_with(|c: char| c.is_ascii_digit()) || name == "_" {
+            name.insert(0, '_');
+        }
+        name
+    };
+    let name = sanitize(char::is_alphanumeric);
+    [name.clone(), format!("r#{name}"), format!("{name}_")]
+        .iter()
+        .find_map(|candidate| syn::parse_str::<Ident>(candidate).ok())
+        .unwrap_or_else(|| format_ident!("{}_", sanitize(|c| c.is_ascii_alphanumeric())))
+}
+
+/// Checks that every `$` placeholder of the query is bound exactly once and
+/// that every binding names a variable. Returns the placeholder names.
+fn check_bindings(input: &MacroInput, text: &str) -> syn::Result<Vec<String>> {
+    let variables = sparql_http_client_scan::variables(text);
+    let placeholders = sparql_http_client_scan::placeholders(text);
+
+    let mut bound = Vec::new();
+    for binding in &input.bindings {
+        let name = binding.name.unraw().to_string();
+        if bound.contains(&name) {
+            return Err(syn::Error::new_spanned(
+                &binding.name,
+                format!("`{name}` is bound more than once"),
+            ));
+        }
+        if !variables.iter().any(|(_, v)| *v == name) {
+            return Err(syn::Error::new_spanned(
+                &binding.name,
+                format!("the query has no `${name}` or `?{name}` variable"),
+            ));
+        }
+        bound.push(name);
+    }
+    if let Some(unbound) = placeholders.iter().find(|p| !bound.contains(p)) {
+        return Err(syn::Error::new_spanned(
+            &input.query_str,
+            format!("placeholder `${unbound}` is not bound; add `{unbound} = <value>`"),
+        ));
+    }
+    Ok(placeholders)
+}
+
+/// Returns the variables projected by a SELECT query pattern.
+///
+/// The projection sits below any solution modifiers other than `ORDER BY`,
+/// which the algebra applies before projecting.
+fn projection(pattern: &GraphPattern) -> Vec<String> {
+    match pattern {
+        GraphPattern::Project { variables, .. } => {
+            variables.iter().map(|v| v.as_str().to_owned()).collect()
+        }
+        GraphPattern::Distinct { inner }
+        | GraphPattern::Reduced { inner }
+        | GraphPattern::Slice { inner, .. }
+        | GraphPattern::OrderBy { inner, .. } => projection(inner),
+        _ => Vec::new(),
+    }
+}
+
+/// Creates a typed SELECT query whose rows are converted with `FromRow`, checking at
+/// compile time that the projection binds every variable the row type requires.
+///
+/// Takes the row type, the endpoint and the query. The syntax is validated as with
+/// [`query!`], and the query must be a `SELECT`. The result is a
+/// `TypedSelectQuery<T>`, whose `.run().await` yields a stream of `T`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[derive(FromRow)]
+/// struct Country {
+///     #[sparql(var = "countryLabel")]
+///     label: String,
+///     population: Option<u64>,
+/// }
+///
+/// let rows = query_as!(Country, endpoint, "SELECT ?countryLabel WHERE { ?c ?p ?countryLabel }")
+///     .run()
+///     .await?;
+/// ```
+#[proc_macro]
+pub fn query_as(input: TokenStream) -> TokenStream {
+    let QueryAsInput { row_type, input } = parse_macro_input!(input as QueryAsInput);
+    if let Err(e) = input.reject_bindings("query_as!") {
+        return e.to_compile_error().into();
+    }
     let MacroInput {
         endpoint,
         query_str,
-    } = parse_macro_input!(input as MacroInput);
+        ..
+    } = input;
 
     let parsed = match parse_sparql(&query_str) {
         Ok(q) => q,
         Err(e) => return e.to_compile_error().into(),
     };
 
-    let qs_type = match query_string_type(&parsed, &query_str) {
-        Ok(t) => t,
+    let Query::Select { pattern, .. } = &parsed else {
+        return syn::Error::new_spanned(&query_str, "query_as! requires a SELECT query")
+            .to_compile_error()
+            .into();
+    };
+    let projected = projection(pattern);
+
+    let qs_type = query_string_type(&parsed);
+    let query = build_query_expr(&endpoint, qs_type, &parsed.to_string());
+    let check = quote::quote_spanned! {query_str.span()=>
+        const _: () = ::sparql_http_client::__private::assert_projected(
+            <#row_type as ::sparql_http_client::FromRow>::REQUIRED_VARS,
+            &[#(#projected),*],
+        );
+    };
+
+    quote! {
+        {
+            #check
+            #query.rows_as::<#row_type>()
+        }
+    }
+    .into()
+}
+
+/// Derives `FromRow`, converting a SELECT result row into a struct with named fields.
+///
+/// Each field reads the variable of the same name; `Option<T>` fields may be
+/// unbound. Field attributes:
+///
+/// - `#[sparql(var = "name")]` reads another variable;
+/// - `#[sparql(with = path)]` converts the term with `fn(&RDFTerm) -> Result<T, E>`
+///   where `E: Display`, instead of the serde-based default.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[derive(FromRow)]
+/// struct Country {
+///     #[sparql(var = "countryLabel")]
+///     label: String,
+///     population: Option<u64>,
+/// }
+/// ```
+#[proc_macro_derive(FromRow, attributes(sparql))]
+pub fn derive_from_row(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match from_row_impl(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+struct FieldAttrs {
+    var: Option<LitStr>,
+    with: Option<Path>,
+}
+
+fn field_attrs(field: &syn::Field) -> syn::Result<FieldAttrs> {
+    let mut attrs = FieldAttrs {
+        var: None,
+        with: None,
+    };
+    for attr in field.attrs.iter().filter(|a| a.path().is_ident("sparql")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("var") {
+                attrs.var = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("with") {
+                attrs.with = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `var` or `with`"))
+            }
+        })?;
+    }
+    Ok(attrs)
+}
+
+/// Returns `true` for types spelled `Option<...>`, including qualified paths.
+fn is_option(ty: &Type) -> bool {
+    let Type::Path(path) = ty else {
+        return false;
+    };
+    path.qself.is_none()
+        && path.path.segments.last().is_some_and(|segment| {
+            segment.ident == "Option"
+                && matches!(segment.arguments, syn::PathArguments::AngleBracketed(_))
+        })
+}
+
+fn from_row_impl(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    input,
+                    "FromRow can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                input,
+                "FromRow can only be derived for structs with named fields",
+            ))
+        }
+    };
+
+    let mut vars = Vec::new();
+    let mut required_vars = Vec::new();
+    let mut inits = Vec::new();
+    for field in fields {
+        let ident = field.ident.as_ref().expect("named field");
+        let attrs = field_attrs(field)?;
+        let var = match attrs.var {
+            Some(var) => var.value(),
+            None => ident.to_string().trim_start_matches("r#").to_owned(),
+        };
+        let convert = match attrs.with {
+            Some(path) => quote! { #path },
+            None => quote! { ::sparql_http_client::response::from_term },
+        };
+        let read = if is_option(&field.ty) {
+            quote! { optional }
+        } else {
+            required_vars.push(var.clone());
+            quote! { required }
+        };
+        inits.push(quote! {
+            #ident: ::sparql_http_client::__private::#read(row, #var, #convert)?
+        });
+        vars.push(var);
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::sparql_http_client::FromRow for #name #ty_generics #where_clause {
+            const VARS: &'static [&'static str] = &[#(#vars),*];
+            const REQUIRED_VARS: &'static [&'static str] = &[#(#required_vars),*];
+
+            fn from_row(
+                row: &::sparql_http_client::Row,
+            ) -> ::std::result::Result<Self, ::sparql_http_client::RowError> {
+                ::std::result::Result::Ok(Self {
+                    #(#inits,)*
+                })
+            }
+        }
+    })
+}
+
+/// Creates a SPARQL update future with compile-time SPARQL Update syntax validation.
+///
+/// Expands to a call to `Endpoint::update` with an already validated and
+/// normalised `UpdateString`, so `.await` sends it straight to the endpoint's
+/// update URL.
+///
+/// A malformed update is a **compile error**.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// update!(endpoint, "INSERT DATA { <http://example.org/s> <http://example.org/p> 1 }").await?;
+/// ```
+#[proc_macro]
+pub fn update(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as MacroInput);
+    if let Err(e) = input.reject_bindings("update!") {
+        return e.to_compile_error().into();
+    }
+    let MacroInput {
+        endpoint,
+        query_str: update_str,
+        ..
+    } = input;
+
+    let parsed = match parse_sparql_update(&update_str) {
+        Ok(u) => u,
         Err(e) => return e.to_compile_error().into(),
     };
 
-    build_query_expr(&endpoint, qs_type, &parsed.to_string()).into()
+    let normalized = parsed.to_string();
+    quote! {
+        (#endpoint).update(::sparql_http_client::UpdateString::new_unchecked(#normalized))
+    }
+    .into()
 }
@@ -0,0 +1,156 @@
+//! Compile errors for SPARQL syntax errors, pointing at the offending position.
+
+use spargebra::SparqlSyntaxError;
+use syn::LitStr;
+
+/// Builds the compile error for a syntax error in the query written in `lit`.
+///
+/// The error is spanned at the offending character when the compiler can
+/// resolve spans inside literals (nightly only), and at the whole literal
+/// otherwise. The message shows the offending line with a caret under the
+/// error position either way.
+pub(crate) fn in_literal(lit: &LitStr, error: &SparqlSyntaxError) -> syn::Error {
+    let text = lit.value();
+    let message = error.to_string();
+    let Some((line, column, details)) = location(&message) else {
+        return syn::Error::new_spanned(lit, format!("SPARQL syntax error: {message}"));
+    };
+
+    let span = value_offset(&text, line, column)
+        .and_then(|offset| source_offset(&lit.token().to_string(), offset))
+        .and_then(|start| lit.token().subspan(start..start + 1))
+        .unwrap_or_else(|| lit.span());
+    syn::Error::new(
+        span,
+        format!(
+            "SPARQL syntax error at {line}:{column}: {details}\n{}",
+            excerpt(&text, line, column)
+        ),
+    )
+}
+
+/// Builds the compile error for a syntax error in the query file `path`,
+/// spanned at the path literal `lit`.
+pub(crate) fn in_file(
+    lit: &LitStr,
+    path: &str,
+    text: &str,
+    error: &SparqlSyntaxError,
+) -> syn::Error {
+    let message = error.to_string();
+    let message = match location(&message) {
+        Some((line, column, details)) => format!(
+            "{path}:{line}:{column}: SPARQL syntax error: {details}\n{}",
+            excerpt(text, line, column)
+        ),
+        None => format!("{path}: SPARQL syntax error: {message}"),
+    };
+    syn::Error::new_spanned(lit, message)
+}
+
+/// Splits a spargebra syntax error message, `error at LINE:COLUMN: details`,
+/// into its 1-based line and column and the details.
+fn location(message: &str) -> Option<(usize, usize, &str)> {
+    let rest = message.strip_prefix("error at ")?;
+    let (position, details) = rest.split_once(": ")?;
+    let (line, column) = position.split_once(':')?;
+    Some((line.parse().ok()?, column.parse().ok()?, details))
+}
+
+/// Renders line `line` of `text` with a caret under `column`, both 1-based
+/// and counted in characters.
+fn excerpt(text: &str, line: usize, column: usize) -> String {
+    let source = text.lines().nth(line - 1).unwrap_or_default();
+    // Tabs are kept so that the caret lines up however they are rendered.
+    let padding: String = source
+        .chars()
+        .take(column - 1)
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect();
+    let number = line.to_string();
+    let gutter = " ".repeat(number.len());
+    format!("{gutter} |\n{number} | {source}\n{gutter} | {padding}^")
+}
+
+/// Converts a 1-based line and character column into a byte offset in `text`.
+fn value_offset(text: &str, line: usize, column: usize) -> Option<usize> {
+    let start: usize = text
+        .split_inclusive('\n')
+        .take(line - 1)
+        .map(str::len)
+        .sum();
+    let rest = &text[start..];
+    match rest.char_indices().nth(column - 1) {
+        Some((offset, _)) => Some(start + offset),
+        None => Some(start + rest.len()),
+    }
+}
+
+/// Converts a byte offset in the value of a string literal into a byte offset
+/// in its source `token`, skipping the opening quote and resolving escapes.
+fn source_offset(token: &str, offset: usize) -> Option<usize> {
+    if let Some(raw) = token.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        return Some(1 + hashes + 1 + offset);
+    }
+
+    let source = token.strip_prefix('"')?;
+    let mut chars = source.char_indices().peekable();
+    let mut value = 0;
+    while let Some((i, c)) = chars.next() {
+        if value >= offset {
+            return Some(1 + i);
+        }
+        if c != '\\' {
+            value += c.len_utf8();
+            continue;
+        }
+        match chars.next()?.1 {
+            'x' => {
+                chars.next();
+                chars.next();
+                value += 1;
+            }
+            'u' => {
+                let mut hex = String::new();
+                for (_, d) in chars.by_ref() {
+                    match d {
+                        '}' => break,
+                        '{' | '_' => {}
+                        d => hex.push(d),
+                    }
+                }
+                value += char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?.len_utf8();
+            }
+            '\n' => while chars.next_if(|(_, d)| d.is_whitespace()).is_some() {},
+            _ => value += 1,
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn caret_excerpt() {
+        let text = "SELECT ?s WHERE {\n\t?s ?p ?o .\n\tFILTER(?o = )\n}";
+        assert_eq!(
+            excerpt(text, 3, 14),
+            "  |\n3 | \tFILTER(?o = )\n  | \t            ^"
+        );
+        assert_eq!(&text[value_offset(text, 3, 14).unwrap()..], ")\n}");
+    }
+
+    #[test]
+    fn offsets_in_source() {
+        // The value of `"a\n\"é\" b"` is `a`, newline, `"é"`, space, `b`.
+        let token = r#""a\n\"é\" b""#;
+        assert_eq!(source_offset(token, 0), Some(1));
+        assert_eq!(source_offset(token, 2), Some(4));
+        assert_eq!(&token[source_offset(token, 7).unwrap()..], "b\"");
+        assert_eq!(source_offset(r#""\u{e9}b""#, 2), Some(7));
+        assert_eq!(source_offset("r#\"ab\"#", 1), Some(4));
+    }
+}
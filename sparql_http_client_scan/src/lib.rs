@@ -0,0 +1,97 @@
+//! Query text scanning shared by `sparql_http_client` and its macros, so that
+//! the placeholders checked at compile time are the ones bound at runtime.
+//!
+//! Internal to `sparql_http_client`; not public API.
+
+/// Lists the variables written in `query` with their `?` or `$` sigil, in order
+/// of first appearance.
+///
+/// Strings, IRIs and comments are skipped so that a `$` inside them is not
+/// taken for a placeholder.
+pub fn variables(query: &str) -> Vec<(char, String)> {
+    let mut found: Vec<(char, String)> = Vec::new();
+    let mut chars = query.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '#' => {
+                for (_, c) in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            '"' | '\'' => {
+                let long = query[i..].starts_with(&c.to_string().repeat(3));
+                if long {
+                    chars.next();
+                    chars.next();
+                }
+                let mut quotes = 0;
+                while let Some((_, d)) = chars.next() {
+                    if d == '\\' {
+                        chars.next();
+                        quotes = 0;
+                    } else if d == c {
+                        quotes += 1;
+                        if !long || quotes == 3 {
+                            break;
+                        }
+                    } else {
+                        quotes = 0;
+                    }
+                }
+            }
+            '<' => {
+                // `<` is an IRI only if a `>` closes it before any character IRIs exclude.
+                let rest = &query[i + 1..];
+                if let Some(end) =
+                    rest.find(|c: char| c == '>' || c.is_whitespace() || "<\"{}|^`".contains(c))
+                {
+                    if rest[end..].starts_with('>') {
+                        for _ in rest[..=end].chars() {
+                            chars.next();
+                        }
+                    }
+                }
+            }
+            '?' | '$' => {
+                let mut name = String::new();
+                while let Some(&(_, d)) = chars.peek() {
+                    if d.is_alphanumeric() || d == '_' || d == '\u{B7}' {
+                        name.push(d);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                if !name.is_empty() && !found.iter().any(|(s, n)| *s == c && *n == name) {
+                    found.push((c, name));
+                }
+            }
+            _ => {}
+        }
+    }
+    found
+}
+
+/// Returns the names of the `$` placeholders among the variables of `query`.
+pub fn placeholders(query: &str) -> Vec<String> {
+    variables(query)
+        .into_iter()
+        .filter(|(sigil, _)| *sigil == '$')
+        .map(|(_, name)| name)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scan_placeholders() {
+        let query = r#"SELECT ?s WHERE { ?s <http://ex.org/$iri> "$str" . # $comment
+               FILTER(?s < $max && '''$long ''' != $max) }"#;
+        assert_eq!(variables(query), [('?', "s".into()), ('$', "max".into())],);
+        assert_eq!(placeholders(query), ["max"]);
+    }
+}